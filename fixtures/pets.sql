@@ -0,0 +1,1382 @@
+--
+-- PostgreSQL database dump
+--
+
+
+-- Dumped from database version 15.18 (Debian 15.18-0+deb12u1)
+-- Dumped by pg_dump version 15.18 (Debian 15.18-0+deb12u1)
+
+SET statement_timeout = 0;
+SET lock_timeout = 0;
+SET idle_in_transaction_session_timeout = 0;
+SET client_encoding = 'SQL_ASCII';
+SET standard_conforming_strings = on;
+SELECT pg_catalog.set_config('search_path', '', false);
+SET check_function_bodies = false;
+SET xmloption = content;
+SET client_min_messages = warning;
+SET row_security = off;
+
+--
+-- Name: citext; Type: EXTENSION; Schema: -; Owner: -
+--
+
+CREATE EXTENSION IF NOT EXISTS citext WITH SCHEMA public;
+
+
+--
+-- Name: EXTENSION citext; Type: COMMENT; Schema: -; Owner: -
+--
+
+COMMENT ON EXTENSION citext IS 'data type for case-insensitive character strings';
+
+
+--
+-- Name: mood; Type: TYPE; Schema: public; Owner: -
+--
+
+CREATE TYPE public.mood AS ENUM (
+    'happy',
+    'sad',
+    'so so'
+);
+
+
+--
+-- Name: TYPE mood; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON TYPE public.mood IS 'How a user is feeling';
+
+
+--
+-- Name: address; Type: TYPE; Schema: public; Owner: -
+--
+
+CREATE TYPE public.address AS (
+	street text,
+	city text,
+	moods public.mood[]
+);
+
+
+--
+-- Name: TYPE address; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON TYPE public.address IS 'A postal address';
+
+
+--
+-- Name: email_address; Type: DOMAIN; Schema: public; Owner: -
+--
+
+CREATE DOMAIN public.email_address AS text
+	CONSTRAINT email_address_check CHECK ((VALUE ~~ '%@%'::text));
+
+
+--
+-- Name: location; Type: TYPE; Schema: public; Owner: -
+--
+
+CREATE TYPE public.location AS (
+	label text,
+	address public.address
+);
+
+
+--
+-- Name: positive_int; Type: DOMAIN; Schema: public; Owner: -
+--
+
+CREATE DOMAIN public.positive_int AS integer NOT NULL
+	CONSTRAINT positive_int_check CHECK ((VALUE > 0));
+
+
+SET default_tablespace = '';
+
+SET default_table_access_method = heap;
+
+--
+-- Name: badge; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.badge (
+    id integer NOT NULL,
+    userid integer NOT NULL,
+    name text NOT NULL,
+    date timestamp without time zone NOT NULL,
+    class integer NOT NULL,
+    tagbased integer NOT NULL
+);
+
+
+--
+-- Name: comment; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.comment (
+    id integer NOT NULL,
+    postid integer NOT NULL,
+    score integer NOT NULL,
+    text text NOT NULL,
+    creationdate timestamp without time zone NOT NULL,
+    userid integer
+);
+
+
+--
+-- Name: compound_child_table; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.compound_child_table (
+    id integer NOT NULL,
+    parent_id1 integer NOT NULL,
+    parent_id2 integer NOT NULL
+);
+
+
+--
+-- Name: compound_table; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.compound_table (
+    parent_id1 integer NOT NULL,
+    parent_id2 integer NOT NULL,
+    name text
+);
+
+
+--
+-- Name: foreign_primary_key; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.foreign_primary_key (
+    post_id integer NOT NULL,
+    note text
+);
+
+
+--
+-- Name: mutation_test; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.mutation_test (
+    id integer NOT NULL,
+    name text NOT NULL,
+    nullable_float double precision
+);
+
+
+--
+-- Name: mutation_test_child; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.mutation_test_child (
+    id integer NOT NULL,
+    parent_id integer,
+    value text
+);
+
+
+--
+-- Name: mutation_test_child_id_seq; Type: SEQUENCE; Schema: public; Owner: -
+--
+
+CREATE SEQUENCE public.mutation_test_child_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+--
+-- Name: mutation_test_child_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: -
+--
+
+ALTER SEQUENCE public.mutation_test_child_id_seq OWNED BY public.mutation_test_child.id;
+
+
+--
+-- Name: mutation_test_id_seq; Type: SEQUENCE; Schema: public; Owner: -
+--
+
+CREATE SEQUENCE public.mutation_test_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+--
+-- Name: mutation_test_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: -
+--
+
+ALTER SEQUENCE public.mutation_test_id_seq OWNED BY public.mutation_test.id;
+
+
+--
+-- Name: node; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.node (
+    id integer NOT NULL,
+    node_id integer NOT NULL,
+    name text
+);
+
+
+--
+-- Name: node_id_seq; Type: SEQUENCE; Schema: public; Owner: -
+--
+
+CREATE SEQUENCE public.node_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+--
+-- Name: node_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: -
+--
+
+ALTER SEQUENCE public.node_id_seq OWNED BY public.node.id;
+
+
+--
+-- Name: post; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.post (
+    id integer NOT NULL,
+    posttypeid integer NOT NULL,
+    acceptedanswerid integer,
+    parentid integer,
+    creationdate timestamp without time zone NOT NULL,
+    deletiondate timestamp without time zone,
+    score integer NOT NULL,
+    viewcount integer,
+    body text NOT NULL,
+    owneruserid integer,
+    ownerdisplayname text,
+    lasteditoruserid integer,
+    lasteditordisplayname text,
+    lasteditdate timestamp without time zone,
+    lastactivitydate timestamp without time zone,
+    title text,
+    tags text,
+    answercount integer,
+    commentcount integer,
+    favoritecount integer,
+    closeddate timestamp without time zone
+);
+
+
+--
+-- Name: posthistory; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.posthistory (
+    id integer NOT NULL,
+    posthistorytypeid integer NOT NULL,
+    postid integer NOT NULL,
+    creationdate timestamp without time zone NOT NULL,
+    userid integer,
+    text text,
+    comment text
+);
+
+
+--
+-- Name: postlink; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.postlink (
+    id integer NOT NULL,
+    creationdate timestamp without time zone NOT NULL,
+    postid integer NOT NULL,
+    relatedpostid integer NOT NULL,
+    linktypeid integer NOT NULL
+);
+
+
+--
+-- Name: site_user; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.site_user (
+    id integer NOT NULL,
+    reputation integer NOT NULL,
+    creationdate timestamp without time zone NOT NULL,
+    displayname character varying(40) NOT NULL,
+    lastaccessdate timestamp without time zone,
+    websiteurl text,
+    location text,
+    aboutme text,
+    views integer NOT NULL,
+    upvotes integer NOT NULL,
+    downvotes integer NOT NULL,
+    profileimageurl text,
+    age integer,
+    accountid integer,
+    jsonfield jsonb
+);
+
+
+--
+-- Name: TABLE site_user; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON TABLE public.site_user IS 'A user of the site';
+
+
+--
+-- Name: COLUMN site_user.displayname; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.site_user.displayname IS 'The name shown next to the user''s posts';
+
+
+--
+-- Name: tag; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.tag (
+    id integer NOT NULL,
+    tagname text NOT NULL,
+    count integer NOT NULL,
+    excerptpostid integer,
+    wikipostid integer
+);
+
+
+--
+-- Name: type_test; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.type_test (
+    id integer NOT NULL,
+    small smallint NOT NULL,
+    big bigint NOT NULL,
+    single real,
+    amount numeric(10,2),
+    code character(3),
+    uid uuid,
+    day date,
+    at_time time without time zone,
+    duration interval,
+    bytes bytea,
+    address inet,
+    network cidr,
+    price money,
+    document xml,
+    search tsvector,
+    email public.citext,
+    mood public.mood,
+    tags text[],
+    scores integer[] DEFAULT '{}'::integer[] NOT NULL,
+    moods public.mood[],
+    docs jsonb[],
+    contact public.email_address,
+    rating public.positive_int DEFAULT 1,
+    home public.address,
+    spot public.location,
+    history public.address[],
+    precise numeric,
+    bigs bigint[],
+    happened timestamp with time zone,
+    moments timestamp without time zone[],
+    zoned_time time with time zone
+);
+
+
+--
+-- Name: type_test_id_seq; Type: SEQUENCE; Schema: public; Owner: -
+--
+
+CREATE SEQUENCE public.type_test_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+--
+-- Name: type_test_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: -
+--
+
+ALTER SEQUENCE public.type_test_id_seq OWNED BY public.type_test.id;
+
+
+--
+-- Name: vote; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.vote (
+    id integer NOT NULL,
+    postid integer NOT NULL,
+    votetypeid integer NOT NULL,
+    userid integer,
+    creationdate timestamp without time zone NOT NULL,
+    bountyamount integer
+);
+
+
+--
+-- Name: mutation_test id; Type: DEFAULT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.mutation_test ALTER COLUMN id SET DEFAULT nextval('public.mutation_test_id_seq'::regclass);
+
+
+--
+-- Name: mutation_test_child id; Type: DEFAULT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.mutation_test_child ALTER COLUMN id SET DEFAULT nextval('public.mutation_test_child_id_seq'::regclass);
+
+
+--
+-- Name: node id; Type: DEFAULT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.node ALTER COLUMN id SET DEFAULT nextval('public.node_id_seq'::regclass);
+
+
+--
+-- Name: type_test id; Type: DEFAULT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.type_test ALTER COLUMN id SET DEFAULT nextval('public.type_test_id_seq'::regclass);
+
+
+--
+-- Data for Name: badge; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.badge (id, userid, name, date, class, tagbased) FROM stdin;
+1	2	badge1	2020-01-01 00:00:00	1	0
+2	3	badge2	2020-01-01 00:00:00	2	1
+3	4	badge3	2020-01-01 00:00:00	0	0
+4	5	badge4	2020-01-01 00:00:00	1	1
+5	6	badge5	2020-01-01 00:00:00	2	0
+6	7	badge6	2020-01-01 00:00:00	0	1
+7	8	badge7	2020-01-01 00:00:00	1	0
+8	9	badge8	2020-01-01 00:00:00	2	1
+9	10	badge9	2020-01-01 00:00:00	0	0
+10	11	badge10	2020-01-01 00:00:00	1	1
+11	12	badge11	2020-01-01 00:00:00	2	0
+12	13	badge12	2020-01-01 00:00:00	0	1
+13	14	badge13	2020-01-01 00:00:00	1	0
+14	15	badge14	2020-01-01 00:00:00	2	1
+15	16	badge15	2020-01-01 00:00:00	0	0
+16	17	badge16	2020-01-01 00:00:00	1	1
+17	18	badge17	2020-01-01 00:00:00	2	0
+18	19	badge18	2020-01-01 00:00:00	0	1
+19	20	badge19	2020-01-01 00:00:00	1	0
+20	21	badge20	2020-01-01 00:00:00	2	1
+21	22	badge21	2020-01-01 00:00:00	0	0
+22	23	badge22	2020-01-01 00:00:00	1	1
+23	24	badge23	2020-01-01 00:00:00	2	0
+24	25	badge24	2020-01-01 00:00:00	0	1
+25	26	badge25	2020-01-01 00:00:00	1	0
+26	27	badge26	2020-01-01 00:00:00	2	1
+27	28	badge27	2020-01-01 00:00:00	0	0
+28	29	badge28	2020-01-01 00:00:00	1	1
+29	30	badge29	2020-01-01 00:00:00	2	0
+30	31	badge30	2020-01-01 00:00:00	0	1
+31	32	badge31	2020-01-01 00:00:00	1	0
+32	33	badge32	2020-01-01 00:00:00	2	1
+33	34	badge33	2020-01-01 00:00:00	0	0
+34	35	badge34	2020-01-01 00:00:00	1	1
+35	36	badge35	2020-01-01 00:00:00	2	0
+36	37	badge36	2020-01-01 00:00:00	0	1
+37	38	badge37	2020-01-01 00:00:00	1	0
+38	39	badge38	2020-01-01 00:00:00	2	1
+39	40	badge39	2020-01-01 00:00:00	0	0
+40	1	badge40	2020-01-01 00:00:00	1	1
+41	2	badge41	2020-01-01 00:00:00	2	0
+42	3	badge42	2020-01-01 00:00:00	0	1
+43	4	badge43	2020-01-01 00:00:00	1	0
+44	5	badge44	2020-01-01 00:00:00	2	1
+45	6	badge45	2020-01-01 00:00:00	0	0
+46	7	badge46	2020-01-01 00:00:00	1	1
+47	8	badge47	2020-01-01 00:00:00	2	0
+48	9	badge48	2020-01-01 00:00:00	0	1
+49	10	badge49	2020-01-01 00:00:00	1	0
+50	11	badge50	2020-01-01 00:00:00	2	1
+51	12	badge51	2020-01-01 00:00:00	0	0
+52	13	badge52	2020-01-01 00:00:00	1	1
+53	14	badge53	2020-01-01 00:00:00	2	0
+54	15	badge54	2020-01-01 00:00:00	0	1
+55	16	badge55	2020-01-01 00:00:00	1	0
+56	17	badge56	2020-01-01 00:00:00	2	1
+57	18	badge57	2020-01-01 00:00:00	0	0
+58	19	badge58	2020-01-01 00:00:00	1	1
+59	20	badge59	2020-01-01 00:00:00	2	0
+60	21	badge60	2020-01-01 00:00:00	0	1
+61	22	badge61	2020-01-01 00:00:00	1	0
+62	23	badge62	2020-01-01 00:00:00	2	1
+63	24	badge63	2020-01-01 00:00:00	0	0
+64	25	badge64	2020-01-01 00:00:00	1	1
+65	26	badge65	2020-01-01 00:00:00	2	0
+66	27	badge66	2020-01-01 00:00:00	0	1
+67	28	badge67	2020-01-01 00:00:00	1	0
+68	29	badge68	2020-01-01 00:00:00	2	1
+69	30	badge69	2020-01-01 00:00:00	0	0
+70	31	badge70	2020-01-01 00:00:00	1	1
+71	32	badge71	2020-01-01 00:00:00	2	0
+72	33	badge72	2020-01-01 00:00:00	0	1
+73	34	badge73	2020-01-01 00:00:00	1	0
+74	35	badge74	2020-01-01 00:00:00	2	1
+75	36	badge75	2020-01-01 00:00:00	0	0
+76	37	badge76	2020-01-01 00:00:00	1	1
+77	38	badge77	2020-01-01 00:00:00	2	0
+78	39	badge78	2020-01-01 00:00:00	0	1
+79	40	badge79	2020-01-01 00:00:00	1	0
+80	1	badge80	2020-01-01 00:00:00	2	1
+\.
+
+
+--
+-- Data for Name: comment; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.comment (id, postid, score, text, creationdate, userid) FROM stdin;
+1	2	1	comment 1	2020-07-01 01:00:00	2
+2	3	2	comment 2	2020-07-01 02:00:00	3
+3	4	3	comment 3	2020-07-01 03:00:00	4
+4	5	4	comment 4	2020-07-01 04:00:00	5
+5	6	0	comment 5	2020-07-01 05:00:00	6
+6	7	1	comment 6	2020-07-01 06:00:00	7
+7	8	2	comment 7	2020-07-01 07:00:00	\N
+8	9	3	comment 8	2020-07-01 08:00:00	9
+9	10	4	comment 9	2020-07-01 09:00:00	10
+10	11	0	comment 10	2020-07-01 10:00:00	11
+11	12	1	comment 11	2020-07-01 11:00:00	12
+12	13	2	comment 12	2020-07-01 12:00:00	13
+13	14	3	comment 13	2020-07-01 13:00:00	14
+14	15	4	comment 14	2020-07-01 14:00:00	\N
+15	16	0	comment 15	2020-07-01 15:00:00	16
+16	17	1	comment 16	2020-07-01 16:00:00	17
+17	18	2	comment 17	2020-07-01 17:00:00	18
+18	19	3	comment 18	2020-07-01 18:00:00	19
+19	20	4	comment 19	2020-07-01 19:00:00	20
+20	21	0	comment 20	2020-07-01 20:00:00	21
+21	22	1	comment 21	2020-07-01 21:00:00	\N
+22	23	2	comment 22	2020-07-01 22:00:00	23
+23	24	3	comment 23	2020-07-01 23:00:00	24
+24	25	4	comment 24	2020-07-02 00:00:00	25
+25	26	0	comment 25	2020-07-02 01:00:00	26
+26	27	1	comment 26	2020-07-02 02:00:00	27
+27	28	2	comment 27	2020-07-02 03:00:00	28
+28	29	3	comment 28	2020-07-02 04:00:00	\N
+29	30	4	comment 29	2020-07-02 05:00:00	30
+30	31	0	comment 30	2020-07-02 06:00:00	31
+31	32	1	comment 31	2020-07-02 07:00:00	32
+32	33	2	comment 32	2020-07-02 08:00:00	33
+33	34	3	comment 33	2020-07-02 09:00:00	34
+34	35	4	comment 34	2020-07-02 10:00:00	35
+35	36	0	comment 35	2020-07-02 11:00:00	\N
+36	37	1	comment 36	2020-07-02 12:00:00	37
+37	38	2	comment 37	2020-07-02 13:00:00	38
+38	39	3	comment 38	2020-07-02 14:00:00	39
+39	40	4	comment 39	2020-07-02 15:00:00	40
+40	41	0	comment 40	2020-07-02 16:00:00	1
+41	42	1	comment 41	2020-07-02 17:00:00	2
+42	43	2	comment 42	2020-07-02 18:00:00	\N
+43	44	3	comment 43	2020-07-02 19:00:00	4
+44	45	4	comment 44	2020-07-02 20:00:00	5
+45	46	0	comment 45	2020-07-02 21:00:00	6
+46	47	1	comment 46	2020-07-02 22:00:00	7
+47	48	2	comment 47	2020-07-02 23:00:00	8
+48	49	3	comment 48	2020-07-03 00:00:00	9
+49	50	4	comment 49	2020-07-03 01:00:00	\N
+50	51	0	comment 50	2020-07-03 02:00:00	11
+51	52	1	comment 51	2020-07-03 03:00:00	12
+52	53	2	comment 52	2020-07-03 04:00:00	13
+53	54	3	comment 53	2020-07-03 05:00:00	14
+54	55	4	comment 54	2020-07-03 06:00:00	15
+55	56	0	comment 55	2020-07-03 07:00:00	16
+56	57	1	comment 56	2020-07-03 08:00:00	\N
+57	58	2	comment 57	2020-07-03 09:00:00	18
+58	59	3	comment 58	2020-07-03 10:00:00	19
+59	60	4	comment 59	2020-07-03 11:00:00	20
+60	61	0	comment 60	2020-07-03 12:00:00	21
+61	62	1	comment 61	2020-07-03 13:00:00	22
+62	63	2	comment 62	2020-07-03 14:00:00	23
+63	64	3	comment 63	2020-07-03 15:00:00	\N
+64	65	4	comment 64	2020-07-03 16:00:00	25
+65	66	0	comment 65	2020-07-03 17:00:00	26
+66	67	1	comment 66	2020-07-03 18:00:00	27
+67	68	2	comment 67	2020-07-03 19:00:00	28
+68	69	3	comment 68	2020-07-03 20:00:00	29
+69	70	4	comment 69	2020-07-03 21:00:00	30
+70	71	0	comment 70	2020-07-03 22:00:00	\N
+71	72	1	comment 71	2020-07-03 23:00:00	32
+72	73	2	comment 72	2020-07-04 00:00:00	33
+73	74	3	comment 73	2020-07-04 01:00:00	34
+74	75	4	comment 74	2020-07-04 02:00:00	35
+75	76	0	comment 75	2020-07-04 03:00:00	36
+76	77	1	comment 76	2020-07-04 04:00:00	37
+77	78	2	comment 77	2020-07-04 05:00:00	\N
+78	79	3	comment 78	2020-07-04 06:00:00	39
+79	80	4	comment 79	2020-07-04 07:00:00	40
+80	81	0	comment 80	2020-07-04 08:00:00	1
+81	82	1	comment 81	2020-07-04 09:00:00	2
+82	83	2	comment 82	2020-07-04 10:00:00	3
+83	84	3	comment 83	2020-07-04 11:00:00	4
+84	85	4	comment 84	2020-07-04 12:00:00	\N
+85	86	0	comment 85	2020-07-04 13:00:00	6
+86	87	1	comment 86	2020-07-04 14:00:00	7
+87	88	2	comment 87	2020-07-04 15:00:00	8
+88	89	3	comment 88	2020-07-04 16:00:00	9
+89	90	4	comment 89	2020-07-04 17:00:00	10
+90	91	0	comment 90	2020-07-04 18:00:00	11
+91	92	1	comment 91	2020-07-04 19:00:00	\N
+92	93	2	comment 92	2020-07-04 20:00:00	13
+93	94	3	comment 93	2020-07-04 21:00:00	14
+94	95	4	comment 94	2020-07-04 22:00:00	15
+95	96	0	comment 95	2020-07-04 23:00:00	16
+96	97	1	comment 96	2020-07-05 00:00:00	17
+97	98	2	comment 97	2020-07-05 01:00:00	18
+98	99	3	comment 98	2020-07-05 02:00:00	\N
+99	100	4	comment 99	2020-07-05 03:00:00	20
+100	101	0	comment 100	2020-07-05 04:00:00	21
+101	102	1	comment 101	2020-07-05 05:00:00	22
+102	103	2	comment 102	2020-07-05 06:00:00	23
+103	104	3	comment 103	2020-07-05 07:00:00	24
+104	105	4	comment 104	2020-07-05 08:00:00	25
+105	106	0	comment 105	2020-07-05 09:00:00	\N
+106	107	1	comment 106	2020-07-05 10:00:00	27
+107	108	2	comment 107	2020-07-05 11:00:00	28
+108	109	3	comment 108	2020-07-05 12:00:00	29
+109	110	4	comment 109	2020-07-05 13:00:00	30
+110	111	0	comment 110	2020-07-05 14:00:00	31
+111	112	1	comment 111	2020-07-05 15:00:00	32
+112	113	2	comment 112	2020-07-05 16:00:00	\N
+113	114	3	comment 113	2020-07-05 17:00:00	34
+114	115	4	comment 114	2020-07-05 18:00:00	35
+115	116	0	comment 115	2020-07-05 19:00:00	36
+116	117	1	comment 116	2020-07-05 20:00:00	37
+117	118	2	comment 117	2020-07-05 21:00:00	38
+118	119	3	comment 118	2020-07-05 22:00:00	39
+119	120	4	comment 119	2020-07-05 23:00:00	\N
+120	1	0	comment 120	2020-07-06 00:00:00	1
+121	2	1	comment 121	2020-07-06 01:00:00	2
+122	3	2	comment 122	2020-07-06 02:00:00	3
+123	4	3	comment 123	2020-07-06 03:00:00	4
+124	5	4	comment 124	2020-07-06 04:00:00	5
+125	6	0	comment 125	2020-07-06 05:00:00	6
+126	7	1	comment 126	2020-07-06 06:00:00	\N
+127	8	2	comment 127	2020-07-06 07:00:00	8
+128	9	3	comment 128	2020-07-06 08:00:00	9
+129	10	4	comment 129	2020-07-06 09:00:00	10
+130	11	0	comment 130	2020-07-06 10:00:00	11
+131	12	1	comment 131	2020-07-06 11:00:00	12
+132	13	2	comment 132	2020-07-06 12:00:00	13
+133	14	3	comment 133	2020-07-06 13:00:00	\N
+134	15	4	comment 134	2020-07-06 14:00:00	15
+135	16	0	comment 135	2020-07-06 15:00:00	16
+136	17	1	comment 136	2020-07-06 16:00:00	17
+137	18	2	comment 137	2020-07-06 17:00:00	18
+138	19	3	comment 138	2020-07-06 18:00:00	19
+139	20	4	comment 139	2020-07-06 19:00:00	20
+140	21	0	comment 140	2020-07-06 20:00:00	\N
+141	22	1	comment 141	2020-07-06 21:00:00	22
+142	23	2	comment 142	2020-07-06 22:00:00	23
+143	24	3	comment 143	2020-07-06 23:00:00	24
+144	25	4	comment 144	2020-07-07 00:00:00	25
+145	26	0	comment 145	2020-07-07 01:00:00	26
+146	27	1	comment 146	2020-07-07 02:00:00	27
+147	28	2	comment 147	2020-07-07 03:00:00	\N
+148	29	3	comment 148	2020-07-07 04:00:00	29
+149	30	4	comment 149	2020-07-07 05:00:00	30
+150	31	0	comment 150	2020-07-07 06:00:00	31
+151	32	1	comment 151	2020-07-07 07:00:00	32
+152	33	2	comment 152	2020-07-07 08:00:00	33
+153	34	3	comment 153	2020-07-07 09:00:00	34
+154	35	4	comment 154	2020-07-07 10:00:00	\N
+155	36	0	comment 155	2020-07-07 11:00:00	36
+156	37	1	comment 156	2020-07-07 12:00:00	37
+157	38	2	comment 157	2020-07-07 13:00:00	38
+158	39	3	comment 158	2020-07-07 14:00:00	39
+159	40	4	comment 159	2020-07-07 15:00:00	40
+160	41	0	comment 160	2020-07-07 16:00:00	1
+161	42	1	comment 161	2020-07-07 17:00:00	\N
+162	43	2	comment 162	2020-07-07 18:00:00	3
+163	44	3	comment 163	2020-07-07 19:00:00	4
+164	45	4	comment 164	2020-07-07 20:00:00	5
+165	46	0	comment 165	2020-07-07 21:00:00	6
+166	47	1	comment 166	2020-07-07 22:00:00	7
+167	48	2	comment 167	2020-07-07 23:00:00	8
+168	49	3	comment 168	2020-07-08 00:00:00	\N
+169	50	4	comment 169	2020-07-08 01:00:00	10
+170	51	0	comment 170	2020-07-08 02:00:00	11
+171	52	1	comment 171	2020-07-08 03:00:00	12
+172	53	2	comment 172	2020-07-08 04:00:00	13
+173	54	3	comment 173	2020-07-08 05:00:00	14
+174	55	4	comment 174	2020-07-08 06:00:00	15
+175	56	0	comment 175	2020-07-08 07:00:00	\N
+176	57	1	comment 176	2020-07-08 08:00:00	17
+177	58	2	comment 177	2020-07-08 09:00:00	18
+178	59	3	comment 178	2020-07-08 10:00:00	19
+179	60	4	comment 179	2020-07-08 11:00:00	20
+180	61	0	comment 180	2020-07-08 12:00:00	21
+181	62	1	comment 181	2020-07-08 13:00:00	22
+182	63	2	comment 182	2020-07-08 14:00:00	\N
+183	64	3	comment 183	2020-07-08 15:00:00	24
+184	65	4	comment 184	2020-07-08 16:00:00	25
+185	66	0	comment 185	2020-07-08 17:00:00	26
+186	67	1	comment 186	2020-07-08 18:00:00	27
+187	68	2	comment 187	2020-07-08 19:00:00	28
+188	69	3	comment 188	2020-07-08 20:00:00	29
+189	70	4	comment 189	2020-07-08 21:00:00	\N
+190	71	0	comment 190	2020-07-08 22:00:00	31
+191	72	1	comment 191	2020-07-08 23:00:00	32
+192	73	2	comment 192	2020-07-09 00:00:00	33
+193	74	3	comment 193	2020-07-09 01:00:00	34
+194	75	4	comment 194	2020-07-09 02:00:00	35
+195	76	0	comment 195	2020-07-09 03:00:00	36
+196	77	1	comment 196	2020-07-09 04:00:00	\N
+197	78	2	comment 197	2020-07-09 05:00:00	38
+198	79	3	comment 198	2020-07-09 06:00:00	39
+199	80	4	comment 199	2020-07-09 07:00:00	40
+200	81	0	comment 200	2020-07-09 08:00:00	1
+201	82	1	comment 201	2020-07-09 09:00:00	2
+202	83	2	comment 202	2020-07-09 10:00:00	3
+203	84	3	comment 203	2020-07-09 11:00:00	\N
+204	85	4	comment 204	2020-07-09 12:00:00	5
+205	86	0	comment 205	2020-07-09 13:00:00	6
+206	87	1	comment 206	2020-07-09 14:00:00	7
+207	88	2	comment 207	2020-07-09 15:00:00	8
+208	89	3	comment 208	2020-07-09 16:00:00	9
+209	90	4	comment 209	2020-07-09 17:00:00	10
+210	91	0	comment 210	2020-07-09 18:00:00	\N
+211	92	1	comment 211	2020-07-09 19:00:00	12
+212	93	2	comment 212	2020-07-09 20:00:00	13
+213	94	3	comment 213	2020-07-09 21:00:00	14
+214	95	4	comment 214	2020-07-09 22:00:00	15
+215	96	0	comment 215	2020-07-09 23:00:00	16
+216	97	1	comment 216	2020-07-10 00:00:00	17
+217	98	2	comment 217	2020-07-10 01:00:00	\N
+218	99	3	comment 218	2020-07-10 02:00:00	19
+219	100	4	comment 219	2020-07-10 03:00:00	20
+220	101	0	comment 220	2020-07-10 04:00:00	21
+221	102	1	comment 221	2020-07-10 05:00:00	22
+222	103	2	comment 222	2020-07-10 06:00:00	23
+223	104	3	comment 223	2020-07-10 07:00:00	24
+224	105	4	comment 224	2020-07-10 08:00:00	\N
+225	106	0	comment 225	2020-07-10 09:00:00	26
+226	107	1	comment 226	2020-07-10 10:00:00	27
+227	108	2	comment 227	2020-07-10 11:00:00	28
+228	109	3	comment 228	2020-07-10 12:00:00	29
+229	110	4	comment 229	2020-07-10 13:00:00	30
+230	111	0	comment 230	2020-07-10 14:00:00	31
+231	112	1	comment 231	2020-07-10 15:00:00	\N
+232	113	2	comment 232	2020-07-10 16:00:00	33
+233	114	3	comment 233	2020-07-10 17:00:00	34
+234	115	4	comment 234	2020-07-10 18:00:00	35
+235	116	0	comment 235	2020-07-10 19:00:00	36
+236	117	1	comment 236	2020-07-10 20:00:00	37
+237	118	2	comment 237	2020-07-10 21:00:00	38
+238	119	3	comment 238	2020-07-10 22:00:00	\N
+239	120	4	comment 239	2020-07-10 23:00:00	40
+240	1	0	comment 240	2020-07-11 00:00:00	1
+241	2	1	comment 241	2020-07-11 01:00:00	2
+242	3	2	comment 242	2020-07-11 02:00:00	3
+243	4	3	comment 243	2020-07-11 03:00:00	4
+244	5	4	comment 244	2020-07-11 04:00:00	5
+245	6	0	comment 245	2020-07-11 05:00:00	\N
+246	7	1	comment 246	2020-07-11 06:00:00	7
+247	8	2	comment 247	2020-07-11 07:00:00	8
+248	9	3	comment 248	2020-07-11 08:00:00	9
+249	10	4	comment 249	2020-07-11 09:00:00	10
+250	11	0	comment 250	2020-07-11 10:00:00	11
+251	12	1	comment 251	2020-07-11 11:00:00	12
+252	13	2	comment 252	2020-07-11 12:00:00	\N
+253	14	3	comment 253	2020-07-11 13:00:00	14
+254	15	4	comment 254	2020-07-11 14:00:00	15
+255	16	0	comment 255	2020-07-11 15:00:00	16
+256	17	1	comment 256	2020-07-11 16:00:00	17
+257	18	2	comment 257	2020-07-11 17:00:00	18
+258	19	3	comment 258	2020-07-11 18:00:00	19
+259	20	4	comment 259	2020-07-11 19:00:00	\N
+260	21	0	comment 260	2020-07-11 20:00:00	21
+261	22	1	comment 261	2020-07-11 21:00:00	22
+262	23	2	comment 262	2020-07-11 22:00:00	23
+263	24	3	comment 263	2020-07-11 23:00:00	24
+264	25	4	comment 264	2020-07-12 00:00:00	25
+265	26	0	comment 265	2020-07-12 01:00:00	26
+266	27	1	comment 266	2020-07-12 02:00:00	\N
+267	28	2	comment 267	2020-07-12 03:00:00	28
+268	29	3	comment 268	2020-07-12 04:00:00	29
+269	30	4	comment 269	2020-07-12 05:00:00	30
+270	31	0	comment 270	2020-07-12 06:00:00	31
+271	32	1	comment 271	2020-07-12 07:00:00	32
+272	33	2	comment 272	2020-07-12 08:00:00	33
+273	34	3	comment 273	2020-07-12 09:00:00	\N
+274	35	4	comment 274	2020-07-12 10:00:00	35
+275	36	0	comment 275	2020-07-12 11:00:00	36
+276	37	1	comment 276	2020-07-12 12:00:00	37
+277	38	2	comment 277	2020-07-12 13:00:00	38
+278	39	3	comment 278	2020-07-12 14:00:00	39
+279	40	4	comment 279	2020-07-12 15:00:00	40
+280	41	0	comment 280	2020-07-12 16:00:00	\N
+281	42	1	comment 281	2020-07-12 17:00:00	2
+282	43	2	comment 282	2020-07-12 18:00:00	3
+283	44	3	comment 283	2020-07-12 19:00:00	4
+284	45	4	comment 284	2020-07-12 20:00:00	5
+285	46	0	comment 285	2020-07-12 21:00:00	6
+286	47	1	comment 286	2020-07-12 22:00:00	7
+287	48	2	comment 287	2020-07-12 23:00:00	\N
+288	49	3	comment 288	2020-07-13 00:00:00	9
+289	50	4	comment 289	2020-07-13 01:00:00	10
+290	51	0	comment 290	2020-07-13 02:00:00	11
+291	52	1	comment 291	2020-07-13 03:00:00	12
+292	53	2	comment 292	2020-07-13 04:00:00	13
+293	54	3	comment 293	2020-07-13 05:00:00	14
+294	55	4	comment 294	2020-07-13 06:00:00	\N
+295	56	0	comment 295	2020-07-13 07:00:00	16
+296	57	1	comment 296	2020-07-13 08:00:00	17
+297	58	2	comment 297	2020-07-13 09:00:00	18
+298	59	3	comment 298	2020-07-13 10:00:00	19
+299	60	4	comment 299	2020-07-13 11:00:00	20
+300	61	0	comment 300	2020-07-13 12:00:00	21
+\.
+
+
+--
+-- Data for Name: compound_child_table; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.compound_child_table (id, parent_id1, parent_id2) FROM stdin;
+1	1	1
+2	1	1
+3	1	2
+\.
+
+
+--
+-- Data for Name: compound_table; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.compound_table (parent_id1, parent_id2, name) FROM stdin;
+1	1	a
+1	2	b
+\.
+
+
+--
+-- Data for Name: foreign_primary_key; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.foreign_primary_key (post_id, note) FROM stdin;
+1	x
+2	y
+\.
+
+
+--
+-- Data for Name: mutation_test; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.mutation_test (id, name, nullable_float) FROM stdin;
+1	a	\N
+2	b	\N
+\.
+
+
+--
+-- Data for Name: mutation_test_child; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.mutation_test_child (id, parent_id, value) FROM stdin;
+\.
+
+
+--
+-- Data for Name: node; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.node (id, node_id, name) FROM stdin;
+1	7	first
+\.
+
+
+--
+-- Data for Name: post; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.post (id, posttypeid, acceptedanswerid, parentid, creationdate, deletiondate, score, viewcount, body, owneruserid, ownerdisplayname, lasteditoruserid, lasteditordisplayname, lasteditdate, lastactivitydate, title, tags, answercount, commentcount, favoritecount, closeddate) FROM stdin;
+1	2	\N	\N	2020-06-02 00:00:00	\N	1	5	body 1	2	\N	\N	\N	\N	\N	title '1	<tag>	1	1	\N	\N
+2	1	\N	\N	2020-06-03 00:00:00	\N	2	10	body 2	3	\N	\N	\N	\N	\N	title '2	<tag>	2	2	\N	\N
+3	2	\N	\N	2020-06-04 00:00:00	\N	3	15	body 3	4	\N	\N	\N	\N	\N	title '3	<tag>	3	0	\N	\N
+4	1	\N	\N	2020-06-05 00:00:00	\N	4	20	body 4	5	\N	\N	\N	\N	\N	title '4	<tag>	0	1	\N	\N
+5	2	\N	\N	2020-06-06 00:00:00	\N	5	25	body 5	6	\N	\N	\N	\N	\N	title '5	<tag>	1	2	\N	\N
+6	1	\N	\N	2020-06-07 00:00:00	\N	6	30	body 6	7	\N	\N	\N	\N	\N	title '6	<tag>	2	0	\N	\N
+7	2	\N	\N	2020-06-08 00:00:00	\N	7	35	body 7	8	\N	\N	\N	\N	\N	title '7	<tag>	3	1	\N	\N
+8	1	\N	\N	2020-06-09 00:00:00	\N	8	40	body 8	9	\N	\N	\N	\N	\N	title '8	<tag>	0	2	\N	\N
+9	2	\N	\N	2020-06-10 00:00:00	\N	9	45	body 9	\N	\N	\N	\N	\N	\N	title '9	<tag>	1	0	\N	\N
+10	1	\N	\N	2020-06-11 00:00:00	\N	10	50	body 10	11	\N	\N	\N	\N	\N	title '10	<tag>	2	1	\N	\N
+11	2	\N	\N	2020-06-12 00:00:00	\N	11	55	body 11	12	\N	\N	\N	\N	\N	title '11	<tag>	3	2	\N	\N
+12	1	\N	\N	2020-06-13 00:00:00	\N	12	60	body 12	13	\N	\N	\N	\N	\N	title '12	<tag>	0	0	\N	\N
+13	2	\N	\N	2020-06-14 00:00:00	\N	13	65	body 13	14	\N	\N	\N	\N	\N	title '13	<tag>	1	1	\N	\N
+14	1	\N	\N	2020-06-15 00:00:00	\N	14	70	body 14	15	\N	\N	\N	\N	\N	title '14	<tag>	2	2	\N	\N
+15	2	\N	\N	2020-06-16 00:00:00	\N	15	75	body 15	16	\N	\N	\N	\N	\N	title '15	<tag>	3	0	\N	\N
+16	1	\N	\N	2020-06-17 00:00:00	\N	16	80	body 16	17	\N	\N	\N	\N	\N	title '16	<tag>	0	1	\N	\N
+17	2	\N	\N	2020-06-18 00:00:00	\N	0	85	body 17	18	\N	\N	\N	\N	\N	title '17	<tag>	1	2	\N	\N
+18	1	\N	\N	2020-06-19 00:00:00	\N	1	90	body 18	\N	\N	\N	\N	\N	\N	title '18	<tag>	2	0	\N	\N
+19	2	\N	\N	2020-06-20 00:00:00	\N	2	95	body 19	20	\N	\N	\N	\N	\N	title '19	<tag>	3	1	\N	\N
+20	1	\N	\N	2020-06-21 00:00:00	\N	3	100	body 20	21	\N	\N	\N	\N	\N	title '20	<tag>	0	2	\N	\N
+21	2	\N	\N	2020-06-22 00:00:00	\N	4	105	body 21	22	\N	\N	\N	\N	\N	title '21	<tag>	1	0	\N	\N
+22	1	\N	\N	2020-06-23 00:00:00	\N	5	110	body 22	23	\N	\N	\N	\N	\N	title '22	<tag>	2	1	\N	\N
+23	2	\N	\N	2020-06-24 00:00:00	\N	6	115	body 23	24	\N	\N	\N	\N	\N	title '23	<tag>	3	2	\N	\N
+24	1	\N	\N	2020-06-25 00:00:00	\N	7	120	body 24	25	\N	\N	\N	\N	\N	title '24	<tag>	0	0	\N	\N
+25	2	\N	\N	2020-06-26 00:00:00	\N	8	125	body 25	26	\N	\N	\N	\N	\N	title '25	<tag>	1	1	\N	\N
+26	1	\N	\N	2020-06-27 00:00:00	\N	9	130	body 26	27	\N	\N	\N	\N	\N	title '26	<tag>	2	2	\N	\N
+27	2	\N	\N	2020-06-28 00:00:00	\N	10	135	body 27	\N	\N	\N	\N	\N	\N	title '27	<tag>	3	0	\N	\N
+28	1	\N	\N	2020-06-29 00:00:00	\N	11	140	body 28	29	\N	\N	\N	\N	\N	title '28	<tag>	0	1	\N	\N
+29	2	\N	\N	2020-06-30 00:00:00	\N	12	145	body 29	30	\N	\N	\N	\N	\N	title '29	<tag>	1	2	\N	\N
+30	1	\N	\N	2020-07-01 00:00:00	\N	13	150	body 30	1	\N	\N	\N	\N	\N	title '30	<tag>	2	0	\N	\N
+31	2	\N	\N	2020-07-02 00:00:00	\N	14	155	body 31	2	\N	\N	\N	\N	\N	title '31	<tag>	3	1	\N	\N
+32	1	\N	\N	2020-07-03 00:00:00	\N	15	160	body 32	3	\N	\N	\N	\N	\N	title '32	<tag>	0	2	\N	\N
+33	2	\N	\N	2020-07-04 00:00:00	\N	16	165	body 33	4	\N	\N	\N	\N	\N	title '33	<tag>	1	0	\N	\N
+34	1	\N	\N	2020-07-05 00:00:00	\N	0	170	body 34	5	\N	\N	\N	\N	\N	title '34	<tag>	2	1	\N	\N
+35	2	\N	\N	2020-07-06 00:00:00	\N	1	175	body 35	6	\N	\N	\N	\N	\N	title '35	<tag>	3	2	\N	\N
+36	1	\N	\N	2020-07-07 00:00:00	\N	2	180	body 36	\N	\N	\N	\N	\N	\N	title '36	<tag>	0	0	\N	\N
+37	2	\N	\N	2020-07-08 00:00:00	\N	3	185	body 37	8	\N	\N	\N	\N	\N	title '37	<tag>	1	1	\N	\N
+38	1	\N	\N	2020-07-09 00:00:00	\N	4	190	body 38	9	\N	\N	\N	\N	\N	title '38	<tag>	2	2	\N	\N
+39	2	\N	\N	2020-07-10 00:00:00	\N	5	195	body 39	10	\N	\N	\N	\N	\N	title '39	<tag>	3	0	\N	\N
+40	1	\N	\N	2020-07-11 00:00:00	\N	6	200	body 40	11	\N	\N	\N	\N	\N	title '40	<tag>	0	1	\N	\N
+41	2	\N	\N	2020-07-12 00:00:00	\N	7	205	body 41	12	\N	\N	\N	\N	\N	title '41	<tag>	1	2	\N	\N
+42	1	\N	\N	2020-07-13 00:00:00	\N	8	210	body 42	13	\N	\N	\N	\N	\N	title '42	<tag>	2	0	\N	\N
+43	2	\N	\N	2020-07-14 00:00:00	\N	9	215	body 43	14	\N	\N	\N	\N	\N	title '43	<tag>	3	1	\N	\N
+44	1	\N	\N	2020-07-15 00:00:00	\N	10	220	body 44	15	\N	\N	\N	\N	\N	title '44	<tag>	0	2	\N	\N
+45	2	\N	\N	2020-07-16 00:00:00	\N	11	225	body 45	\N	\N	\N	\N	\N	\N	title '45	<tag>	1	0	\N	\N
+46	1	\N	\N	2020-07-17 00:00:00	\N	12	230	body 46	17	\N	\N	\N	\N	\N	title '46	<tag>	2	1	\N	\N
+47	2	\N	\N	2020-07-18 00:00:00	\N	13	235	body 47	18	\N	\N	\N	\N	\N	title '47	<tag>	3	2	\N	\N
+48	1	\N	\N	2020-07-19 00:00:00	\N	14	240	body 48	19	\N	\N	\N	\N	\N	title '48	<tag>	0	0	\N	\N
+49	2	\N	\N	2020-07-20 00:00:00	\N	15	245	body 49	20	\N	\N	\N	\N	\N	title '49	<tag>	1	1	\N	\N
+50	1	\N	\N	2020-07-21 00:00:00	\N	16	250	body 50	21	\N	\N	\N	\N	\N	title '50	<tag>	2	2	\N	\N
+51	2	\N	\N	2020-07-22 00:00:00	\N	0	255	body 51	22	\N	\N	\N	\N	\N	title '51	<tag>	3	0	\N	\N
+52	1	\N	\N	2020-07-23 00:00:00	\N	1	260	body 52	23	\N	\N	\N	\N	\N	title '52	<tag>	0	1	\N	\N
+53	2	\N	\N	2020-07-24 00:00:00	\N	2	265	body 53	24	\N	\N	\N	\N	\N	title '53	<tag>	1	2	\N	\N
+54	1	\N	\N	2020-07-25 00:00:00	\N	3	270	body 54	\N	\N	\N	\N	\N	\N	title '54	<tag>	2	0	\N	\N
+55	2	\N	\N	2020-07-26 00:00:00	\N	4	275	body 55	26	\N	\N	\N	\N	\N	title '55	<tag>	3	1	\N	\N
+56	1	\N	\N	2020-07-27 00:00:00	\N	5	280	body 56	27	\N	\N	\N	\N	\N	title '56	<tag>	0	2	\N	\N
+57	2	\N	\N	2020-07-28 00:00:00	\N	6	285	body 57	28	\N	\N	\N	\N	\N	title '57	<tag>	1	0	\N	\N
+58	1	\N	\N	2020-07-29 00:00:00	\N	7	290	body 58	29	\N	\N	\N	\N	\N	title '58	<tag>	2	1	\N	\N
+59	2	\N	\N	2020-07-30 00:00:00	\N	8	295	body 59	30	\N	\N	\N	\N	\N	title '59	<tag>	3	2	\N	\N
+60	1	\N	\N	2020-07-31 00:00:00	\N	9	300	body 60	1	\N	\N	\N	\N	\N	title '60	<tag>	0	0	\N	\N
+61	2	\N	\N	2020-08-01 00:00:00	\N	10	305	body 61	2	\N	\N	\N	\N	\N	title '61	<tag>	1	1	\N	\N
+62	1	\N	\N	2020-08-02 00:00:00	\N	11	310	body 62	3	\N	\N	\N	\N	\N	title '62	<tag>	2	2	\N	\N
+63	2	\N	\N	2020-08-03 00:00:00	\N	12	315	body 63	\N	\N	\N	\N	\N	\N	title '63	<tag>	3	0	\N	\N
+64	1	\N	\N	2020-08-04 00:00:00	\N	13	320	body 64	5	\N	\N	\N	\N	\N	title '64	<tag>	0	1	\N	\N
+65	2	\N	\N	2020-08-05 00:00:00	\N	14	325	body 65	6	\N	\N	\N	\N	\N	title '65	<tag>	1	2	\N	\N
+66	1	\N	\N	2020-08-06 00:00:00	\N	15	330	body 66	7	\N	\N	\N	\N	\N	title '66	<tag>	2	0	\N	\N
+67	2	\N	\N	2020-08-07 00:00:00	\N	16	335	body 67	8	\N	\N	\N	\N	\N	title '67	<tag>	3	1	\N	\N
+68	1	\N	\N	2020-08-08 00:00:00	\N	0	340	body 68	9	\N	\N	\N	\N	\N	title '68	<tag>	0	2	\N	\N
+69	2	\N	\N	2020-08-09 00:00:00	\N	1	345	body 69	10	\N	\N	\N	\N	\N	title '69	<tag>	1	0	\N	\N
+70	1	\N	\N	2020-08-10 00:00:00	\N	2	350	body 70	11	\N	\N	\N	\N	\N	title '70	<tag>	2	1	\N	\N
+71	2	\N	\N	2020-08-11 00:00:00	\N	3	355	body 71	12	\N	\N	\N	\N	\N	title '71	<tag>	3	2	\N	\N
+72	1	\N	\N	2020-08-12 00:00:00	\N	4	360	body 72	\N	\N	\N	\N	\N	\N	title '72	<tag>	0	0	\N	\N
+73	2	\N	\N	2020-08-13 00:00:00	\N	5	365	body 73	14	\N	\N	\N	\N	\N	title '73	<tag>	1	1	\N	\N
+74	1	\N	\N	2020-08-14 00:00:00	\N	6	370	body 74	15	\N	\N	\N	\N	\N	title '74	<tag>	2	2	\N	\N
+75	2	\N	\N	2020-08-15 00:00:00	\N	7	375	body 75	16	\N	\N	\N	\N	\N	title '75	<tag>	3	0	\N	\N
+76	1	\N	\N	2020-08-16 00:00:00	\N	8	380	body 76	17	\N	\N	\N	\N	\N	title '76	<tag>	0	1	\N	\N
+77	2	\N	\N	2020-08-17 00:00:00	\N	9	385	body 77	18	\N	\N	\N	\N	\N	title '77	<tag>	1	2	\N	\N
+78	1	\N	\N	2020-08-18 00:00:00	\N	10	390	body 78	19	\N	\N	\N	\N	\N	title '78	<tag>	2	0	\N	\N
+79	2	\N	\N	2020-08-19 00:00:00	\N	11	395	body 79	20	\N	\N	\N	\N	\N	title '79	<tag>	3	1	\N	\N
+80	1	\N	\N	2020-08-20 00:00:00	\N	12	400	body 80	21	\N	\N	\N	\N	\N	title '80	<tag>	0	2	\N	\N
+81	2	\N	\N	2020-08-21 00:00:00	\N	13	405	body 81	\N	\N	\N	\N	\N	\N	title '81	<tag>	1	0	\N	\N
+82	1	\N	\N	2020-08-22 00:00:00	\N	14	410	body 82	23	\N	\N	\N	\N	\N	title '82	<tag>	2	1	\N	\N
+83	2	\N	\N	2020-08-23 00:00:00	\N	15	415	body 83	24	\N	\N	\N	\N	\N	title '83	<tag>	3	2	\N	\N
+84	1	\N	\N	2020-08-24 00:00:00	\N	16	420	body 84	25	\N	\N	\N	\N	\N	title '84	<tag>	0	0	\N	\N
+85	2	\N	\N	2020-08-25 00:00:00	\N	0	425	body 85	26	\N	\N	\N	\N	\N	title '85	<tag>	1	1	\N	\N
+86	1	\N	\N	2020-08-26 00:00:00	\N	1	430	body 86	27	\N	\N	\N	\N	\N	title '86	<tag>	2	2	\N	\N
+87	2	\N	\N	2020-08-27 00:00:00	\N	2	435	body 87	28	\N	\N	\N	\N	\N	title '87	<tag>	3	0	\N	\N
+88	1	\N	\N	2020-08-28 00:00:00	\N	3	440	body 88	29	\N	\N	\N	\N	\N	title '88	<tag>	0	1	\N	\N
+89	2	\N	\N	2020-08-29 00:00:00	\N	4	445	body 89	30	\N	\N	\N	\N	\N	title '89	<tag>	1	2	\N	\N
+90	1	\N	\N	2020-08-30 00:00:00	\N	5	450	body 90	\N	\N	\N	\N	\N	\N	title '90	<tag>	2	0	\N	\N
+91	2	\N	\N	2020-08-31 00:00:00	\N	6	455	body 91	2	\N	\N	\N	\N	\N	title '91	<tag>	3	1	\N	\N
+92	1	\N	\N	2020-09-01 00:00:00	\N	7	460	body 92	3	\N	\N	\N	\N	\N	title '92	<tag>	0	2	\N	\N
+93	2	\N	\N	2020-09-02 00:00:00	\N	8	465	body 93	4	\N	\N	\N	\N	\N	title '93	<tag>	1	0	\N	\N
+94	1	\N	\N	2020-09-03 00:00:00	\N	9	470	body 94	5	\N	\N	\N	\N	\N	title '94	<tag>	2	1	\N	\N
+95	2	\N	\N	2020-09-04 00:00:00	\N	10	475	body 95	6	\N	\N	\N	\N	\N	title '95	<tag>	3	2	\N	\N
+96	1	\N	\N	2020-09-05 00:00:00	\N	11	480	body 96	7	\N	\N	\N	\N	\N	title '96	<tag>	0	0	\N	\N
+97	2	\N	\N	2020-09-06 00:00:00	\N	12	485	body 97	8	\N	\N	\N	\N	\N	title '97	<tag>	1	1	\N	\N
+98	1	\N	\N	2020-09-07 00:00:00	\N	13	490	body 98	9	\N	\N	\N	\N	\N	title '98	<tag>	2	2	\N	\N
+99	2	\N	\N	2020-09-08 00:00:00	\N	14	495	body 99	\N	\N	\N	\N	\N	\N	title '99	<tag>	3	0	\N	\N
+100	1	\N	\N	2020-09-09 00:00:00	\N	15	500	body 100	11	\N	\N	\N	\N	\N	title '100	<tag>	0	1	\N	\N
+101	2	\N	\N	2020-09-10 00:00:00	\N	16	505	body 101	12	\N	\N	\N	\N	\N	title '101	<tag>	1	2	\N	\N
+102	1	\N	\N	2020-09-11 00:00:00	\N	0	510	body 102	13	\N	\N	\N	\N	\N	title '102	<tag>	2	0	\N	\N
+103	2	\N	\N	2020-09-12 00:00:00	\N	1	515	body 103	14	\N	\N	\N	\N	\N	title '103	<tag>	3	1	\N	\N
+104	1	\N	\N	2020-09-13 00:00:00	\N	2	520	body 104	15	\N	\N	\N	\N	\N	title '104	<tag>	0	2	\N	\N
+105	2	\N	\N	2020-09-14 00:00:00	\N	3	525	body 105	16	\N	\N	\N	\N	\N	title '105	<tag>	1	0	\N	\N
+106	1	\N	\N	2020-09-15 00:00:00	\N	4	530	body 106	17	\N	\N	\N	\N	\N	title '106	<tag>	2	1	\N	\N
+107	2	\N	\N	2020-09-16 00:00:00	\N	5	535	body 107	18	\N	\N	\N	\N	\N	title '107	<tag>	3	2	\N	\N
+108	1	\N	\N	2020-09-17 00:00:00	\N	6	540	body 108	\N	\N	\N	\N	\N	\N	title '108	<tag>	0	0	\N	\N
+109	2	\N	\N	2020-09-18 00:00:00	\N	7	545	body 109	20	\N	\N	\N	\N	\N	title '109	<tag>	1	1	\N	\N
+110	1	\N	\N	2020-09-19 00:00:00	\N	8	550	body 110	21	\N	\N	\N	\N	\N	title '110	<tag>	2	2	\N	\N
+111	2	\N	\N	2020-09-20 00:00:00	\N	9	555	body 111	22	\N	\N	\N	\N	\N	title '111	<tag>	3	0	\N	\N
+112	1	\N	\N	2020-09-21 00:00:00	\N	10	560	body 112	23	\N	\N	\N	\N	\N	title '112	<tag>	0	1	\N	\N
+113	2	\N	\N	2020-09-22 00:00:00	\N	11	565	body 113	24	\N	\N	\N	\N	\N	title '113	<tag>	1	2	\N	\N
+114	1	\N	\N	2020-09-23 00:00:00	\N	12	570	body 114	25	\N	\N	\N	\N	\N	title '114	<tag>	2	0	\N	\N
+115	2	\N	\N	2020-09-24 00:00:00	\N	13	575	body 115	26	\N	\N	\N	\N	\N	title '115	<tag>	3	1	\N	\N
+116	1	\N	\N	2020-09-25 00:00:00	\N	14	580	body 116	27	\N	\N	\N	\N	\N	title '116	<tag>	0	2	\N	\N
+117	2	\N	\N	2020-09-26 00:00:00	\N	15	585	body 117	\N	\N	\N	\N	\N	\N	title '117	<tag>	1	0	\N	\N
+118	1	\N	\N	2020-09-27 00:00:00	\N	16	590	body 118	29	\N	\N	\N	\N	\N	title '118	<tag>	2	1	\N	\N
+119	2	\N	\N	2020-09-28 00:00:00	\N	0	595	body 119	30	\N	\N	\N	\N	\N	title '119	<tag>	3	2	\N	\N
+120	1	\N	\N	2020-09-29 00:00:00	\N	1	600	body 120	1	\N	\N	\N	\N	\N	title '120	<tag>	0	0	\N	\N
+\.
+
+
+--
+-- Data for Name: posthistory; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.posthistory (id, posthistorytypeid, postid, creationdate, userid, text, comment) FROM stdin;
+\.
+
+
+--
+-- Data for Name: postlink; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.postlink (id, creationdate, postid, relatedpostid, linktypeid) FROM stdin;
+\.
+
+
+--
+-- Data for Name: site_user; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.site_user (id, reputation, creationdate, displayname, lastaccessdate, websiteurl, location, aboutme, views, upvotes, downvotes, profileimageurl, age, accountid, jsonfield) FROM stdin;
+1	10	2020-01-02 00:00:00	user1	2021-01-01 01:00:00	\N	loc1	about "me" 1	2	3	1	\N	21	1001	\N
+2	20	2020-01-03 00:00:00	user2	2021-01-01 02:00:00	http://example.com/2	loc2	about "me" 2	4	6	2	\N	22	1002	\N
+3	30	2020-01-04 00:00:00	user3	\N	\N	loc3	about "me" 3	6	9	3	\N	23	1003	\N
+4	40	2020-01-05 00:00:00	user4	2021-01-01 04:00:00	http://example.com/4	loc4	about "me" 4	8	12	4	\N	\N	1004	\N
+5	50	2020-01-06 00:00:00	user5	2021-01-01 05:00:00	\N	loc5	about "me" 5	10	15	5	\N	25	1005	{"a": [1, 2]}
+6	60	2020-01-07 00:00:00	user6	\N	http://example.com/6	loc6	about "me" 6	12	18	6	\N	26	1006	\N
+7	70	2020-01-08 00:00:00	user7	2021-01-01 07:00:00	\N	loc7	about "me" 7	14	21	7	\N	27	1007	\N
+8	80	2020-01-09 00:00:00	user8	2021-01-01 08:00:00	http://example.com/8	loc8	about "me" 8	16	24	8	\N	\N	1008	\N
+9	90	2020-01-10 00:00:00	user9	\N	\N	loc9	about "me" 9	18	27	9	\N	29	1009	\N
+10	100	2020-01-11 00:00:00	user10	2021-01-01 10:00:00	http://example.com/10	loc10	about "me" 10	20	30	10	\N	30	1010	{"a": [1, 2]}
+11	110	2020-01-12 00:00:00	user11	2021-01-01 11:00:00	\N	loc11	about "me" 11	22	33	11	\N	31	1011	\N
+12	120	2020-01-13 00:00:00	user12	\N	http://example.com/12	loc12	about "me" 12	24	36	12	\N	\N	1012	\N
+13	130	2020-01-14 00:00:00	user13	2021-01-01 13:00:00	\N	loc13	about "me" 13	26	39	13	\N	33	1013	\N
+14	140	2020-01-15 00:00:00	user14	2021-01-01 14:00:00	http://example.com/14	loc14	about "me" 14	28	42	14	\N	34	1014	\N
+15	150	2020-01-16 00:00:00	user15	\N	\N	loc15	about "me" 15	30	45	15	\N	35	1015	{"a": [1, 2]}
+16	160	2020-01-17 00:00:00	user16	2021-01-01 16:00:00	http://example.com/16	loc16	about "me" 16	32	48	16	\N	\N	1016	\N
+17	170	2020-01-18 00:00:00	user17	2021-01-01 17:00:00	\N	loc17	about "me" 17	34	51	17	\N	37	1017	\N
+18	180	2020-01-19 00:00:00	user18	\N	http://example.com/18	loc18	about "me" 18	36	54	18	\N	38	1018	\N
+19	190	2020-01-20 00:00:00	user19	2021-01-01 19:00:00	\N	loc19	about "me" 19	38	57	19	\N	39	1019	\N
+20	200	2020-01-21 00:00:00	user20	2021-01-01 20:00:00	http://example.com/20	loc20	about "me" 20	40	60	20	\N	\N	1020	{"a": [1, 2]}
+21	210	2020-01-22 00:00:00	user21	\N	\N	loc21	about "me" 21	42	63	21	\N	41	1021	\N
+22	220	2020-01-23 00:00:00	user22	2021-01-01 22:00:00	http://example.com/22	loc22	about "me" 22	44	66	22	\N	42	1022	\N
+23	230	2020-01-24 00:00:00	user23	2021-01-01 23:00:00	\N	loc23	about "me" 23	46	69	23	\N	43	1023	\N
+24	240	2020-01-25 00:00:00	user24	\N	http://example.com/24	loc24	about "me" 24	48	72	24	\N	\N	1024	\N
+25	250	2020-01-26 00:00:00	user25	2021-01-02 01:00:00	\N	loc25	about "me" 25	50	75	25	\N	45	1025	{"a": [1, 2]}
+26	260	2020-01-27 00:00:00	user26	2021-01-02 02:00:00	http://example.com/26	loc26	about "me" 26	52	78	26	\N	46	1026	\N
+27	270	2020-01-28 00:00:00	user27	\N	\N	loc27	about "me" 27	54	81	27	\N	47	1027	\N
+28	280	2020-01-29 00:00:00	user28	2021-01-02 04:00:00	http://example.com/28	loc28	about "me" 28	56	84	28	\N	\N	1028	\N
+29	290	2020-01-30 00:00:00	user29	2021-01-02 05:00:00	\N	loc29	about "me" 29	58	87	29	\N	49	1029	\N
+30	300	2020-01-31 00:00:00	user30	\N	http://example.com/30	loc30	about "me" 30	60	90	30	\N	50	1030	{"a": [1, 2]}
+31	310	2020-02-01 00:00:00	user31	2021-01-02 07:00:00	\N	loc31	about "me" 31	62	93	31	\N	51	1031	\N
+32	320	2020-02-02 00:00:00	user32	2021-01-02 08:00:00	http://example.com/32	loc32	about "me" 32	64	96	32	\N	\N	1032	\N
+33	330	2020-02-03 00:00:00	user33	\N	\N	loc33	about "me" 33	66	99	33	\N	53	1033	\N
+34	340	2020-02-04 00:00:00	user34	2021-01-02 10:00:00	http://example.com/34	loc34	about "me" 34	68	102	34	\N	54	1034	\N
+35	350	2020-02-05 00:00:00	user35	2021-01-02 11:00:00	\N	loc35	about "me" 35	70	105	35	\N	55	1035	{"a": [1, 2]}
+36	360	2020-02-06 00:00:00	user36	\N	http://example.com/36	loc36	about "me" 36	72	108	36	\N	\N	1036	\N
+37	370	2020-02-07 00:00:00	user37	2021-01-02 13:00:00	\N	loc37	about "me" 37	74	111	37	\N	57	1037	\N
+38	380	2020-02-08 00:00:00	user38	2021-01-02 14:00:00	http://example.com/38	loc38	about "me" 38	76	114	38	\N	58	1038	\N
+39	390	2020-02-09 00:00:00	user39	\N	\N	loc39	about "me" 39	78	117	39	\N	59	1039	\N
+40	400	2020-02-10 00:00:00	user40	2021-01-02 16:00:00	http://example.com/40	loc40	about "me" 40	80	120	40	\N	\N	1040	{"a": [1, 2]}
+\.
+
+
+--
+-- Data for Name: tag; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.tag (id, tagname, count, excerptpostid, wikipostid) FROM stdin;
+\.
+
+
+--
+-- Data for Name: type_test; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.type_test (id, small, big, single, amount, code, uid, day, at_time, duration, bytes, address, network, price, document, search, email, mood, tags, scores, moods, docs, contact, rating, home, spot, history, precise, bigs, happened, moments, zoned_time) FROM stdin;
+1	1	9007199254740993	1.5	12.34	abc	a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11	2020-01-02	12:34:56	1 day 02:00:00	\\x0102	192.168.0.1	10.0.0.0/8	$1.50	<a>b</a>	'cat' 'fat'	A@Example.com	so so	{a,"b,c",NULL}	{1,2,3}	{happy,"so so"}	{"{\\"x\\": 1}"}	a@b.c	1	("1 Main St",Springfield,{sad})	(work,"(""2 Side St"",,)")	{"(\\"old st\\",x,)"}	12345678901234567890.123456789	{9007199254740993,NULL}	2021-01-01 08:00:00.5+00	{"2021-01-01 10:00:00.123456"}	12:34:56+05:30
+2	2	2	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	\N	{}	\N	\N	\N	1	\N	\N	\N	\N	\N	\N	\N	\N
+\.
+
+
+--
+-- Data for Name: vote; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.vote (id, postid, votetypeid, userid, creationdate, bountyamount) FROM stdin;
+\.
+
+
+--
+-- Name: mutation_test_child_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
+--
+
+SELECT pg_catalog.setval('public.mutation_test_child_id_seq', 1, false);
+
+
+--
+-- Name: mutation_test_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
+--
+
+SELECT pg_catalog.setval('public.mutation_test_id_seq', 66, true);
+
+
+--
+-- Name: node_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
+--
+
+SELECT pg_catalog.setval('public.node_id_seq', 1, true);
+
+
+--
+-- Name: type_test_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
+--
+
+SELECT pg_catalog.setval('public.type_test_id_seq', 2, true);
+
+
+--
+-- Name: badge badge_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.badge
+    ADD CONSTRAINT badge_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: comment comments_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.comment
+    ADD CONSTRAINT comments_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: compound_child_table compound_child_table_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.compound_child_table
+    ADD CONSTRAINT compound_child_table_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: compound_table compound_table_parent_id1_name_key; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.compound_table
+    ADD CONSTRAINT compound_table_parent_id1_name_key UNIQUE (parent_id1, name);
+
+
+--
+-- Name: compound_table compound_table_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.compound_table
+    ADD CONSTRAINT compound_table_pkey PRIMARY KEY (parent_id1, parent_id2);
+
+
+--
+-- Name: foreign_primary_key foreign_primary_key_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.foreign_primary_key
+    ADD CONSTRAINT foreign_primary_key_pkey PRIMARY KEY (post_id);
+
+
+--
+-- Name: mutation_test_child mutation_test_child_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.mutation_test_child
+    ADD CONSTRAINT mutation_test_child_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: mutation_test mutation_test_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.mutation_test
+    ADD CONSTRAINT mutation_test_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: node node_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.node
+    ADD CONSTRAINT node_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: post post_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.post
+    ADD CONSTRAINT post_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: posthistory posthistory_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.posthistory
+    ADD CONSTRAINT posthistory_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: postlink postlink_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.postlink
+    ADD CONSTRAINT postlink_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: site_user site_user_accountid_key; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.site_user
+    ADD CONSTRAINT site_user_accountid_key UNIQUE (accountid);
+
+
+--
+-- Name: site_user site_user_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.site_user
+    ADD CONSTRAINT site_user_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: tag tag_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.tag
+    ADD CONSTRAINT tag_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: type_test type_test_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.type_test
+    ADD CONSTRAINT type_test_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: vote vote_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.vote
+    ADD CONSTRAINT vote_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: badge badge_userid_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.badge
+    ADD CONSTRAINT badge_userid_fkey FOREIGN KEY (userid) REFERENCES public.site_user(id);
+
+
+--
+-- Name: compound_child_table compound_child_table_parent_id1_parent_id2_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.compound_child_table
+    ADD CONSTRAINT compound_child_table_parent_id1_parent_id2_fkey FOREIGN KEY (parent_id1, parent_id2) REFERENCES public.compound_table(parent_id1, parent_id2);
+
+
+--
+-- Name: comment fk_comment_post; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.comment
+    ADD CONSTRAINT fk_comment_post FOREIGN KEY (postid) REFERENCES public.post(id);
+
+
+--
+-- Name: comment fk_comment_site_user; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.comment
+    ADD CONSTRAINT fk_comment_site_user FOREIGN KEY (userid) REFERENCES public.site_user(id);
+
+
+--
+-- Name: foreign_primary_key foreign_primary_key_post_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.foreign_primary_key
+    ADD CONSTRAINT foreign_primary_key_post_id_fkey FOREIGN KEY (post_id) REFERENCES public.post(id);
+
+
+--
+-- Name: mutation_test_child mutation_test_child_parent_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.mutation_test_child
+    ADD CONSTRAINT mutation_test_child_parent_id_fkey FOREIGN KEY (parent_id) REFERENCES public.mutation_test(id);
+
+
+--
+-- Name: post post_owneruserid_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.post
+    ADD CONSTRAINT post_owneruserid_fkey FOREIGN KEY (owneruserid) REFERENCES public.site_user(id);
+
+
+--
+-- Name: posthistory posthistory_postid_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.posthistory
+    ADD CONSTRAINT posthistory_postid_fkey FOREIGN KEY (postid) REFERENCES public.post(id);
+
+
+--
+-- Name: postlink postlink_postid_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.postlink
+    ADD CONSTRAINT postlink_postid_fkey FOREIGN KEY (postid) REFERENCES public.post(id);
+
+
+--
+-- Name: vote vote_postid_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.vote
+    ADD CONSTRAINT vote_postid_fkey FOREIGN KEY (postid) REFERENCES public.post(id);
+
+
+--
+-- PostgreSQL database dump complete
+--
+
+
@@ -1,5 +1,5 @@
 use crate::build_schema::postgraphile_introspection::ClassData;
-use crate::build_schema::Operation;
+use crate::build_schema::{GraphQLType, Operation};
 use convert_case::{Case, Casing};
 use inflector::Inflector;
 use petgraph::prelude::NodeIndex;
@@ -10,7 +10,22 @@ pub fn build_mutation(
     node: NodeIndex<u32>,
     field_to_operation: &mut HashMap<String, Operation>,
     class: &ClassData,
+    include_mutations: bool,
+    table: &GraphQLType,
 ) {
+    //the suffixes of the operations on each unique constraint, e.g ByEmail
+    let unique_keys = table
+        .unique_constraints
+        .iter()
+        .map(|cols| {
+            let cols = cols
+                .iter()
+                .map(|col| col.to_case(Case::UpperCamel))
+                .collect::<Vec<String>>();
+            ["By", &cols.join("And")].concat()
+        })
+        .enumerate()
+        .collect::<Vec<(usize, String)>>();
     if class.is_selectable {
         field_to_operation.insert(
             class.name.to_case(Case::Camel),
@@ -20,23 +35,51 @@ pub fn build_mutation(
             class.name.to_case(Case::Camel).to_plural(),
             Operation::Query(true, node),
         );
+        for (key, suffix) in &unique_keys {
+            field_to_operation.insert(
+                [&class.name.to_case(Case::Camel), suffix.as_str()].concat(),
+                Operation::QueryByUnique(node, *key),
+            );
+        }
+        //the cursors of connections are built from primary keys
+        if !table.primary_keys.is_empty() {
+            field_to_operation.insert(
+                [&class.name.to_case(Case::Camel).to_plural(), "Connection"].concat(),
+                Operation::Connection(node),
+            );
+        }
     }
-    if class.is_deletable {
+    if !include_mutations {
+        return;
+    }
+    if class.is_insertable {
         field_to_operation.insert(
-            ["delete", &class.name.to_case(Case::UpperCamel)].concat(),
-            Operation::Delete(node),
+            ["insert", &class.name.to_case(Case::UpperCamel)].concat(),
+            Operation::Insert(node),
         );
     }
-    if class.is_insertable {
+    if class.is_updatable {
         field_to_operation.insert(
             ["update", &class.name.to_case(Case::UpperCamel)].concat(),
             Operation::Update(node),
         );
+        for (key, suffix) in &unique_keys {
+            field_to_operation.insert(
+                ["update", &class.name.to_case(Case::UpperCamel), suffix].concat(),
+                Operation::UpdateByUnique(node, *key),
+            );
+        }
     }
     if class.is_deletable {
         field_to_operation.insert(
-            ["insert", &class.name.to_case(Case::UpperCamel)].concat(),
-            Operation::Insert(node),
+            ["delete", &class.name.to_case(Case::UpperCamel)].concat(),
+            Operation::Delete(node),
         );
+        for (key, suffix) in &unique_keys {
+            field_to_operation.insert(
+                ["delete", &class.name.to_case(Case::UpperCamel), suffix].concat(),
+                Operation::DeleteByUnique(node, *key),
+            );
+        }
     }
 }
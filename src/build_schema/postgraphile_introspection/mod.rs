@@ -69,6 +69,9 @@ pub struct ConstraintData {
     pub key_attribute_nums: Vec<i32>,
     pub foreign_key_attribute_nums: Vec<i32>,
     pub is_indexed: Option<bool>,
+    //f for foreign keys, p for primary keys and u for unique constraints
+    #[serde(rename = "type")]
+    pub constraint_type: String,
     //class: PgClass,
     //foreign_class: Option<PgClass>,
     //key_attributes: Vec<AttributeData>,
@@ -76,7 +79,6 @@ pub struct ConstraintData {
     //namespace: PgNamespace,
     //tags: SmartTags,
     //is_fake: bool,
-    //type: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -157,6 +159,7 @@ impl PostgresEntity {
 mod test {
     use super::*;
     use crate::build_schema::get_schema_and_client;
+    use crate::settings::Settings;
     use query::introspection_query_data;
     use std::collections::HashSet;
 
@@ -168,7 +171,7 @@ mod test {
             class_map,
             attribute_map,
             constraint_map: _,
-        } = introspection_query_data(&client).await;
+        } = introspection_query_data(&client, &Settings::new().unwrap().schemas).await;
         let post_class = class_map
             .values()
             .find(|class| class.name == "post")
@@ -194,7 +197,7 @@ mod test {
             class_map,
             attribute_map: _,
             constraint_map,
-        } = introspection_query_data(&client).await;
+        } = introspection_query_data(&client, &Settings::new().unwrap().schemas).await;
 
         //get the comment classes id
         let comment_id = &class_map
@@ -220,7 +223,9 @@ mod test {
     #[actix_rt::test]
     async fn all_tables_present() {
         let (_, client) = get_schema_and_client().await;
-        let class_map = introspection_query_data(&client).await.class_map;
+        let class_map = introspection_query_data(&client, &Settings::new().unwrap().schemas)
+            .await
+            .class_map;
         let expected_names = [
             "postlink",
             "comment",
@@ -235,6 +240,8 @@ mod test {
             "posthistory",
             "tag",
             "vote",
+            "type_test",
+            "node",
         ];
         for expected_name in expected_names {
             assert!(
@@ -243,9 +250,13 @@ mod test {
                 expected_name
             );
         }
+        //composite types are classes too, but they can't be selected from
         assert_eq!(
             expected_names.len(),
-            class_map.len(),
+            class_map
+                .values()
+                .filter(|class| class.is_selectable)
+                .count(),
             "{:?}",
             class_map
                 .values()
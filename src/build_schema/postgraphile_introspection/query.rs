@@ -8,8 +8,8 @@ pub struct IntrospectionOutput {
     pub attribute_map: HashMap<(String, i32), AttributeData>,
     pub type_map: HashMap<String, TypeData>
 }
-pub async fn introspection_query_data(client: &Client) -> IntrospectionOutput {
-    let rows = client.query(&*make_instrospection_query(999999999, false, false), &[]).await.unwrap();
+pub async fn introspection_query_data(client: &Client, schemas: &[String]) -> IntrospectionOutput {
+    let rows = client.query(&*make_instrospection_query(999999999, false, false), &[&schemas]).await.unwrap();
     let mut output = IntrospectionOutput {
         class_map: HashMap::new(),
         constraint_map: HashMap::new(),
@@ -71,7 +71,7 @@ with ",
       pg_catalog.pg_namespace as nsp
       left join pg_catalog.pg_description as dsc on dsc.objoid = nsp.oid and dsc.classoid = 'pg_catalog.pg_namespace'::regclass
     where
-      nsp.nspname = 'public'
+      nsp.nspname = any($1)
     order by
       nsp.nspname
   ),
@@ -210,7 +210,7 @@ with ",
       -- We don't want classes that will clash with GraphQL (treat them as private)
       rel.relname not like E'\\\\_\\\\_%' and
       rel.relkind in ('r', 'v', 'm', 'c', 'f') and
-      nsp.nspname = 'public' and
+      nsp.nspname = any($1) and
       (not exists(
         select 1
         from pg_catalog.pg_depend
@@ -1,5 +1,7 @@
 use super::*;
+use crate::build_schema::field_to_operation::build_mutation;
 use crate::build_schema::get_schema_and_client;
+use crate::build_schema::postgraphile_introspection::ClassData;
 use petgraph::graph::Edge;
 fn assert_some_edge_eq(
     field_names: (&str, &str),
@@ -122,6 +124,115 @@ async fn foreign_primary_key() {
     assert_eq!(g[edge].incoming_node_cols, vec!["post_id"]);
 }
 
+#[actix_rt::test]
+async fn unique_constraints() {
+    let (pogg, _) = get_schema_and_client().await;
+    let g = pogg.g;
+    let find_node = |table_name: &str| {
+        g.node_weights()
+            .find(|node| node.table_name == table_name)
+            .unwrap()
+    };
+    //unique constraints aren't mistaken for primary keys
+    let site_user = find_node("site_user");
+    assert_eq!(site_user.primary_keys, vec!["id"]);
+    assert_eq!(site_user.unique_constraints, vec![vec!["accountid"]]);
+    let compound_table = find_node("compound_table");
+    assert_eq!(
+        compound_table.primary_keys,
+        vec!["parent_id1", "parent_id2"]
+    );
+    assert_eq!(
+        compound_table.unique_constraints,
+        vec![vec!["parent_id1", "name"]]
+    );
+    for name in [
+        "siteUserByAccountid",
+        "updateSiteUserByAccountid",
+        "deleteSiteUserByAccountid",
+        "compoundTableByParentId1AndName",
+    ] {
+        assert!(pogg.field_to_operation.contains_key(name), "{}", name);
+    }
+}
+
+#[test]
+fn mixed_privileges() {
+    //a table that rows can be inserted into and updated, but not deleted from
+    let class = ClassData {
+        id: String::from("1"),
+        name: String::from("audit_log"),
+        comment: None,
+        description: None,
+        class_kind: String::from("r"),
+        namespace_id: String::from("2200"),
+        namespace_name: String::from("public"),
+        type_id: String::from("1"),
+        is_selectable: true,
+        is_insertable: true,
+        is_updatable: true,
+        is_deletable: false,
+        acl_selectable: true,
+        acl_insertable: true,
+        acl_updatable: true,
+        acl_deletable: false,
+    };
+    let table = GraphQLType {
+        class_id: String::from("1"),
+        namespace_name: String::from("public"),
+        table_name: String::from("audit_log"),
+        primary_keys: vec![String::from("id")],
+        unique_constraints: vec![vec![String::from("entry")]],
+        field_to_types: HashMap::new(),
+        fields_with_defaults: HashSet::new(),
+        description: None,
+        field_descriptions: HashMap::new(),
+    };
+    let mut field_to_operation = HashMap::new();
+    build_mutation(
+        NodeIndex::new(0),
+        &mut field_to_operation,
+        &class,
+        true,
+        &table,
+    );
+    let mut names = field_to_operation.keys().cloned().collect::<Vec<String>>();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "auditLog",
+            "auditLogByEntry",
+            "auditLogs",
+            "auditLogsConnection",
+            "insertAuditLog",
+            "updateAuditLog",
+            "updateAuditLogByEntry",
+        ]
+    );
+
+    //and one that can only be deleted from
+    let class = ClassData {
+        is_insertable: false,
+        is_updatable: false,
+        is_deletable: true,
+        ..class
+    };
+    let mut field_to_operation = HashMap::new();
+    build_mutation(
+        NodeIndex::new(0),
+        &mut field_to_operation,
+        &class,
+        true,
+        &table,
+    );
+    assert!(field_to_operation.contains_key("deleteAuditLog"));
+    assert!(field_to_operation.contains_key("deleteAuditLogByEntry"));
+    assert!(!field_to_operation.contains_key("insertAuditLog"));
+    assert!(!field_to_operation.contains_key("updateAuditLog"));
+    assert!(!field_to_operation.contains_key("updateAuditLogByEntry"));
+}
+
 #[actix_rt::test]
 async fn field_to_operation() {
     let (pogg, _) = get_schema_and_client().await;
@@ -131,7 +242,6 @@ async fn field_to_operation() {
         "{}",
         field_to_operation
             .keys()
-            .into_iter()
             .fold(String::new(), |a, b| format!("{}\n{}", a, b))
     )
 }
@@ -161,6 +271,10 @@ async fn post_has_correct_num_fields() {
     );
 }
 
+fn nullable(pg_type: PostgresType) -> PostgresType {
+    PostgresType::Nullable(Box::new(pg_type))
+}
+
 #[actix_rt::test]
 async fn check_nullability() {
     let (pogg, _) = get_schema_and_client().await;
@@ -174,17 +288,17 @@ async fn check_nullability() {
         ("reputation", PostgresType::Int),
         ("creationdate", PostgresType::Timestamp),
         ("displayname", PostgresType::Str),
-        ("lastaccessdate", PostgresType::NullableTimestamp),
-        ("websiteurl", PostgresType::NullableStr),
-        ("location", PostgresType::NullableStr),
-        ("aboutme", PostgresType::NullableStr),
+        ("lastaccessdate", nullable(PostgresType::Timestamp)),
+        ("websiteurl", nullable(PostgresType::Str)),
+        ("location", nullable(PostgresType::Str)),
+        ("aboutme", nullable(PostgresType::Str)),
         ("views", PostgresType::Int),
         ("upvotes", PostgresType::Int),
         ("downvotes", PostgresType::Int),
-        ("profileimageurl", PostgresType::NullableStr),
-        ("age", PostgresType::NullableInt),
-        ("accountid", PostgresType::NullableInt),
-        ("jsonfield", PostgresType::NullableJson),
+        ("profileimageurl", nullable(PostgresType::Str)),
+        ("age", nullable(PostgresType::Int)),
+        ("accountid", nullable(PostgresType::Int)),
+        ("jsonfield", nullable(PostgresType::Json)),
     ];
     for (key, expected) in expected_column_types {
         assert_eq!(user_node.field_to_types.get(key).unwrap().1, expected);
@@ -215,3 +329,58 @@ async fn test_delete_comment_mutation() {
 //        .unwrap();
 //    assert_some
 //}
+
+#[actix_rt::test]
+async fn type_catalogue() {
+    let (pogg, _) = get_schema_and_client().await;
+    let type_node = pogg
+        .g
+        .node_weights()
+        .find(|n| n.table_name == "type_test")
+        .unwrap();
+    let expected_column_types = vec![
+        ("small", PostgresType::SmallInt),
+        ("big", PostgresType::BigInt),
+        ("single", nullable(PostgresType::Real)),
+        ("amount", nullable(PostgresType::Numeric)),
+        ("code", nullable(PostgresType::Char)),
+        ("uid", nullable(PostgresType::Uuid)),
+        ("day", nullable(PostgresType::Date)),
+        ("atTime", nullable(PostgresType::Time)),
+        ("zonedTime", nullable(PostgresType::Timetz)),
+        ("duration", nullable(PostgresType::Interval)),
+        ("bytes", nullable(PostgresType::Bytea)),
+        ("address", nullable(PostgresType::Inet)),
+        ("network", nullable(PostgresType::Inet)),
+        ("price", nullable(PostgresType::Money)),
+        ("document", nullable(PostgresType::Xml)),
+        ("email", nullable(PostgresType::Citext)),
+        //unmapped types are kept by their schema qualified name rather than panicking
+        (
+            "search",
+            nullable(PostgresType::Other(String::from(
+                "\"pg_catalog\".\"tsvector\"",
+            ))),
+        ),
+    ];
+    for (key, expected) in expected_column_types {
+        assert_eq!(type_node.field_to_types.get(key).unwrap().1, expected);
+    }
+}
+
+#[actix_rt::test]
+async fn domains_keep_their_base_type_and_not_null() {
+    let (pogg, _) = get_schema_and_client().await;
+    let type_node = pogg
+        .g
+        .node_weights()
+        .find(|n| n.table_name == "type_test")
+        .unwrap();
+    assert_eq!(
+        type_node.field_to_types["contact"].1,
+        nullable(PostgresType::Str)
+    );
+    assert_eq!(type_node.field_to_types["rating"].1, PostgresType::Int);
+    //composite types aren't tables
+    assert!(pogg.g.node_weights().all(|n| n.table_name != "address"));
+}
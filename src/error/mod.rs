@@ -0,0 +1,158 @@
+use async_graphql_parser::{Error as ParserError, Pos};
+use derive_more::Display;
+use serde_json::{json, Value as JsonValue};
+
+//why a request failed. Where the spec (or graphql-js) defines an error, its message is used
+#[derive(Debug, Display, PartialEq)]
+pub enum ErrorKind {
+    #[display(fmt = "{}", _0)]
+    Syntax(String),
+    //the operation to execute couldn't be chosen
+    #[display(fmt = "{}", _0)]
+    Operation(String),
+    #[display(fmt = "{}", _0)]
+    Fragment(String),
+    #[display(fmt = "{}", _0)]
+    Variable(String),
+    #[display(fmt = "{}", _0)]
+    Directive(String),
+    #[display(fmt = "No operation named \"{}\"", _0)]
+    UnknownRootField(String),
+    //the type and the name of the field
+    #[display(fmt = "{} does not have selection {}", _0, _1)]
+    UnknownField(String, String),
+    #[display(fmt = "Expected input field {}", _0)]
+    MissingArgument(String),
+    #[display(fmt = "Received unexpected argument {}", _0)]
+    UnknownArgument(String),
+    //the name of the argument, and the type and name of the field it was given to
+    #[display(fmt = "Unknown argument \"{}\" on field \"{}.{}\".", _0, _1, _2)]
+    UnknownFieldArgument(String, String, String),
+    //the name and the type of the field
+    #[display(
+        fmt = "Field \"{}\" of type \"{}\" must have a selection of subfields.",
+        _0,
+        _1
+    )]
+    MissingSelection(String, String),
+    #[display(
+        fmt = "Field \"{}\" must not have a selection since type \"{}\" has no subfields.",
+        _0,
+        _1
+    )]
+    UnexpectedSelection(String, String),
+    //the response key shared by the fields, and why they can't be merged
+    #[display(
+        fmt = "Fields \"{}\" conflict because {}. Use different aliases on the fields to fetch \
+               both if this was intentional.",
+        _0,
+        _1
+    )]
+    FieldsConflict(String, String),
+    //an argument or variable value that doesn't match the type it is used as
+    #[display(fmt = "{}", _0)]
+    InvalidValue(String),
+    #[display(fmt = "{}", _0)]
+    Database(String),
+    #[display(fmt = "{}", _0)]
+    Internal(String),
+}
+
+//an error along with where in the query it occured: the position of the offending node and the
+//response keys of the fields leading to it
+#[derive(Debug, Display, PartialEq)]
+#[display(fmt = "{}", kind)]
+pub struct GraphQLError {
+    pub kind: ErrorKind,
+    pub pos: Option<Pos>,
+    pub path: Vec<String>,
+}
+
+impl ErrorKind {
+    //the value of extensions.code in the response
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Syntax(_) => "GRAPHQL_PARSE_FAILED",
+            ErrorKind::Operation(_)
+            | ErrorKind::Fragment(_)
+            | ErrorKind::Directive(_)
+            | ErrorKind::UnknownRootField(_)
+            | ErrorKind::UnknownField(_, _)
+            | ErrorKind::MissingArgument(_)
+            | ErrorKind::UnknownArgument(_)
+            | ErrorKind::UnknownFieldArgument(_, _, _)
+            | ErrorKind::MissingSelection(_, _)
+            | ErrorKind::UnexpectedSelection(_, _)
+            | ErrorKind::FieldsConflict(_, _) => "GRAPHQL_VALIDATION_FAILED",
+            ErrorKind::Variable(_) | ErrorKind::InvalidValue(_) => "BAD_USER_INPUT",
+            ErrorKind::Database(_) => "DATABASE_ERROR",
+            ErrorKind::Internal(_) => "INTERNAL_SERVER_ERROR",
+        }
+    }
+}
+
+impl GraphQLError {
+    pub fn new(kind: ErrorKind, pos: Pos) -> GraphQLError {
+        GraphQLError {
+            kind,
+            pos: Some(pos),
+            path: vec![],
+        }
+    }
+
+    //errors are raised at the most specific node that is known, so the position is only filled
+    //in by callers if it is still missing
+    pub fn or_at(mut self, pos: Pos) -> GraphQLError {
+        if self.pos.is_none() {
+            self.pos = Some(pos);
+        }
+        self
+    }
+
+    //prepends the response key of the field the error was passed up through
+    pub fn in_field(mut self, key: &str) -> GraphQLError {
+        self.path.insert(0, key.to_string());
+        self
+    }
+
+    //the error in the format of the "errors" entries of a response
+    pub fn to_json(&self) -> JsonValue {
+        let mut error = json!({
+            "message": self.to_string(),
+            "extensions": { "code": self.kind.code() },
+        });
+        if let Some(pos) = self.pos {
+            error["locations"] = json!([{ "line": pos.line, "column": pos.column }]);
+        }
+        if !self.path.is_empty() {
+            error["path"] = json!(self.path);
+        }
+        error
+    }
+}
+
+impl From<ErrorKind> for GraphQLError {
+    fn from(kind: ErrorKind) -> Self {
+        GraphQLError {
+            kind,
+            pos: None,
+            path: vec![],
+        }
+    }
+}
+
+impl From<ParserError> for GraphQLError {
+    fn from(e: ParserError) -> Self {
+        GraphQLError {
+            pos: e.positions().next(),
+            kind: ErrorKind::Syntax(e.to_string()),
+            path: vec![],
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for GraphQLError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        ErrorKind::Database(e.to_string()).into()
+    }
+}
@@ -0,0 +1,61 @@
+use super::response_key;
+use crate::error::{ErrorKind, GraphQLError};
+use crate::schema_model::SchemaModel;
+use async_graphql_parser::types::{Field, Selection, SelectionSet};
+
+//checks that every argument given to a field of selection_set (whose fields belong to
+//parent_type) is one that the field defines. Fields that aren't in the model (such as those of
+//introspection) are left for the SQL generation to check
+pub fn check_arguments(
+    model: &SchemaModel,
+    selection_set: &SelectionSet,
+    parent_type: &str,
+) -> Result<(), GraphQLError> {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => check_field_arguments(model, &field.node, parent_type)
+                .map_err(|e| e.in_field(response_key(&field.node)))?,
+            Selection::InlineFragment(fragment) => {
+                let type_name = match &fragment.node.type_condition {
+                    Some(condition) => condition.node.on.node.as_str(),
+                    None => parent_type,
+                };
+                check_arguments(model, &fragment.node.selection_set.node, type_name)?;
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_field_arguments(
+    model: &SchemaModel,
+    field: &Field,
+    parent_type: &str,
+) -> Result<(), GraphQLError> {
+    let field_definition = match model.field(parent_type, &field.name.node) {
+        Some(field_definition) => field_definition,
+        None => return Ok(()),
+    };
+    for (name, _) in &field.arguments {
+        if !field_definition
+            .arguments
+            .iter()
+            .any(|argument| argument.name == name.node)
+        {
+            return Err(GraphQLError::new(
+                ErrorKind::UnknownFieldArgument(
+                    name.to_string(),
+                    parent_type.to_string(),
+                    field.name.to_string(),
+                ),
+                name.pos,
+            ));
+        }
+    }
+    check_arguments(
+        model,
+        &field.selection_set.node,
+        field_definition.field_type.named_type(),
+    )
+}
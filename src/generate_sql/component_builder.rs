@@ -1,50 +1,26 @@
 use crate::build_schema::PostgresType;
+use crate::error::{ErrorKind, GraphQLError};
 use crate::generate_sql::SqlQueryComponents;
-use async_graphql_parser::{
-    types::{Selection, SelectionSet},
-    Positioned,
-};
+use async_graphql_parser::types::Field;
 use async_graphql_value::{indexmap::IndexMap, Name, Value};
 use std::collections::HashMap;
 
+//table is the table (aliased __table_0__) that is selected from, and condition is that of the
+//where argument, if one was given
 pub fn select(
     sql: &mut SqlQueryComponents,
-    table_name: &str,
+    table: &str,
     is_many: bool,
-    selection_set: &Positioned<SelectionSet>,
-    field_to_types: &HashMap<String, (String, PostgresType)>,
-) -> Result<String, String> {
-    let mut sql_query = [
-        "SELECT ",
-        &sql.selections,
-        " from ",
-        table_name,
-        " AS __table_0__ ",
-        &sql.from,
-    ]
-    .concat();
+    condition: Option<String>,
+) -> Result<String, GraphQLError> {
+    let mut sql_query = ["SELECT ", &sql.selections, " from ", table, " ", &sql.from].concat();
     if !is_many {
         sql_query.push_str(&sql.filter);
     }
-
-    if let Selection::Field(Positioned { pos: _, node }) =
-        &selection_set.node.items.get(0).unwrap().node
-    {
-        //check if where filter was applied
-        if let Some(where_node) = node.get_argument("where") {
-            //ensure its an object
-            if let Value::Object(where_obj) = &where_node.node {
-                sql_query.push_str(" WHERE ");
-
-                //set where equal to values
-                if let Err(e) = assign_cols_vals(&mut sql_query, where_obj, field_to_types, " AND ")
-                {
-                    return Err(e);
-                }
-            } else {
-                return Err(String::from("Where was not an object"));
-            }
-        }
+    if let Some(condition) = condition {
+        //single objects are already filtered by their primary keys
+        sql_query.push_str(if is_many { " WHERE " } else { " AND " });
+        sql_query.push_str(&condition);
     }
     if !sql.order_by.is_empty() {
         sql.order_by
@@ -63,118 +39,123 @@ pub fn delete(sql: &mut SqlQueryComponents, table_name: &str) -> String {
         &sql.filter,
     ]
     .concat();
-    mutation_selections(sql_query, sql).unwrap()
+    mutation_selections(sql_query, sql)
 }
 
 pub fn update(
     sql: &mut SqlQueryComponents,
     table_name: &str,
-    selection_set: &Positioned<SelectionSet>,
+    field: &Field,
     field_to_types: &HashMap<String, (String, PostgresType)>,
-) -> Result<String, String> {
+) -> Result<String, GraphQLError> {
     let mut sql_query = [
         "WITH __table_0__ AS ( UPDATE ",
         table_name,
         " AS __table_0__ SET ",
     ]
     .concat();
-    match &selection_set.node.items.get(0).unwrap().node {
-        Selection::Field(Positioned { pos: _, node }) => match node.get_argument("patch") {
-            Some(patch) => match &patch.node {
-                Value::Object(patch) => {
-                    //set where equal to values
-                    if let Err(e) = assign_cols_vals(&mut sql_query, patch, field_to_types, ",") {
-                        return Err(e);
-                    }
-                }
-                _ => return Err("Patch wasn't an object".to_string()),
-            },
-            None => return Err("Didn't get expected patch input".to_string()),
+    match field.get_argument("patch") {
+        Some(patch) => match &patch.node {
+            Value::Object(patch_obj) if !patch_obj.is_empty() => {
+                //set where equal to values
+                assign_cols_vals(&mut sql_query, sql, patch_obj, field_to_types, ",")
+                    .map_err(|e| e.or_at(patch.pos))?;
+            }
+            Value::Object(_) => {
+                return Err(GraphQLError::new(
+                    ErrorKind::InvalidValue(String::from(
+                        "Expected patch to set at least one column",
+                    )),
+                    patch.pos,
+                ))
+            }
+            other => return Err(expected_object("patch", other).or_at(patch.pos)),
         },
-        _ => panic!("Didn't get Selection::Field"),
+        None => return Err(ErrorKind::MissingArgument(String::from("patch")).into()),
     }
 
     sql_query.push_str(&sql.filter);
-    sql_query.push_str(" RETURNING *) SELECT ");
-    sql_query.push_str(&sql.selections);
-    sql_query.push_str(" from __table_0__");
-    sql_query.push_str(&sql.from);
-    Ok(sql_query)
+    Ok(mutation_selections(sql_query, sql))
 }
 
 pub fn insert(
     sql: &mut SqlQueryComponents,
     table_name: &str,
-    selection_set: &Positioned<SelectionSet>,
+    field: &Field,
     field_to_types: &HashMap<String, (String, PostgresType)>,
-) -> Result<String, String> {
+) -> Result<String, GraphQLError> {
     let mut sql_query = [
         "WITH __table_0__ AS ( INSERT INTO ",
         table_name,
         " AS __table_0__",
     ]
     .concat();
-    match &selection_set.node.items.get(0).unwrap().node {
-        Selection::Field(Positioned { pos: _, node }) => {
-            let mut col_names = String::from("(");
-            let mut vals = String::from(" VALUES(");
-            for (new_name, new_val) in &node.arguments {
-                match field_to_types.get(&new_name.to_string()) {
-                    Some((col_name, _)) => {
-                        col_names.push_str(&col_name.to_string());
-                        vals.push_str(&value_to_string(&new_val.node));
-                        col_names.push(',');
-                        vals.push(',');
-                    }
-                    None => {
-                        return Err(format!("Received unexpected argument {}", new_name));
-                    }
-                }
+    let mut col_names = String::from("(");
+    let mut vals = String::from(" VALUES(");
+    for (new_name, new_val) in &field.arguments {
+        match field_to_types.get(&new_name.to_string()) {
+            Some((col_name, pg_type)) => {
+                col_names.push_str(&col_name.to_string());
+                let placeholder = sql
+                    .push_param(&new_val.node, pg_type)
+                    .map_err(|e| e.or_at(new_val.pos))?;
+                vals.push_str(&placeholder);
+                col_names.push(',');
+                vals.push(',');
+            }
+            None => {
+                return Err(GraphQLError::new(
+                    ErrorKind::UnknownArgument(new_name.to_string()),
+                    new_name.pos,
+                ));
             }
-            //replace trailing commas with close bracket
-            col_names.pop();
-            col_names.push(')');
-            vals.pop();
-            vals.push(')');
-            sql_query.push_str(&col_names);
-            sql_query.push_str(&vals);
         }
-        _ => panic!("Didn't get Selection::Field"),
     }
-    mutation_selections(sql_query, sql)
+    if field.arguments.is_empty() {
+        //every column is left to its default
+        sql_query.push_str(" DEFAULT VALUES");
+    } else {
+        //replace trailing commas with close bracket
+        col_names.pop();
+        col_names.push(')');
+        vals.pop();
+        vals.push(')');
+        sql_query.push_str(&col_names);
+        sql_query.push_str(&vals);
+    }
+    Ok(mutation_selections(sql_query, sql))
 }
 
 fn assign_cols_vals(
     sql_query: &mut String,
+    sql: &mut SqlQueryComponents,
     input_fields: &IndexMap<Name, Value>,
     field_to_types: &HashMap<String, (String, PostgresType)>,
     delimiter: &str,
-) -> Result<(), String> {
-    for arg in input_fields.keys() {
+) -> Result<(), GraphQLError> {
+    for (arg, value) in input_fields {
         match field_to_types.get(&arg.to_string()) {
-            Some((col_name, _)) => sql_query.push_str(
+            Some((col_name, pg_type)) => sql_query.push_str(
                 &[
                     &col_name.to_string(),
                     "=",
-                    &value_to_string(input_fields.get(arg).unwrap()),
+                    &sql.push_param(value, pg_type)?,
                     delimiter,
                 ]
                 .concat(),
             ),
-            None => {
-                return Err(format!("Patch received unexpected argument {}", arg));
-            }
+            None => return Err(ErrorKind::UnknownArgument(arg.to_string()).into()),
         }
     }
     sql_query.drain(sql_query.len() - delimiter.len()..sql_query.len());
     Ok(())
 }
 
-fn mutation_selections(
-    mut sql_query: String,
-    sql: &mut SqlQueryComponents,
-) -> Result<String, String> {
-    sql_query.push_str("RETURNING *) SELECT ");
+//selects the rows returned by the delete, update or insert in sql_query, which all mutations do in
+//the same way
+fn mutation_selections(sql_query: String, sql: &mut SqlQueryComponents) -> String {
+    let mut sql_query = sql_query.trim_end().to_string();
+    sql_query.push_str(" RETURNING *) SELECT ");
     sql_query.push_str(&sql.selections);
     sql_query.push_str(" FROM __table_0__");
     sql_query.push_str(&sql.from);
@@ -184,12 +165,13 @@ fn mutation_selections(
         sql_query.push_str(" ORDER BY ");
         sql_query.push_str(&sql.order_by);
     }
-    Ok(sql_query)
+    sql_query
 }
 
-fn value_to_string(val: &Value) -> String {
-    match val {
-        Value::String(s) => ["'", &s.replace("'", "''"), "'"].concat(),
-        other => other.to_string(),
-    }
+fn expected_object(argument: &str, value: &Value) -> GraphQLError {
+    ErrorKind::InvalidValue(format!(
+        "Expected an object for {}, found {}",
+        argument, value
+    ))
+    .into()
 }
@@ -0,0 +1,130 @@
+use crate::build_schema::{PostgresComposite, PostgresType};
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::response_key;
+use async_graphql_parser::{
+    types::{Field, Selection},
+    Positioned,
+};
+
+//the JSON expression selecting the subfields of field from expr, a composite (or an array of
+//composites) value. Returns it along with the type of the JSON, which only has the selected
+//attributes, named by their response keys. depth is the nesting of arrays, so that the aliases of
+//their elements don't shadow each other
+pub fn select_composite(
+    expr: &str,
+    pg_type: &PostgresType,
+    field: &Positioned<Field>,
+    depth: usize,
+) -> Result<(String, PostgresType), GraphQLError> {
+    match pg_type {
+        PostgresType::Nullable(inner) => {
+            let (sql, selected_type) = select_composite(expr, inner, field, depth)?;
+            Ok((sql, PostgresType::Nullable(Box::new(selected_type))))
+        }
+        PostgresType::Array(element) => {
+            let element_alias = ["__element", &depth.to_string(), "__"].concat();
+            let (sql, selected_type) = select_composite(&element_alias, element, field, depth + 1)?;
+            Ok((
+                [
+                    "CASE WHEN ",
+                    expr,
+                    " IS NULL THEN NULL ELSE coalesce((SELECT json_agg(",
+                    &sql,
+                    ") FROM unnest(",
+                    expr,
+                    ") AS ",
+                    &element_alias,
+                    "), '[]') END",
+                ]
+                .concat(),
+                PostgresType::Array(Box::new(selected_type)),
+            ))
+        }
+        PostgresType::Composite(composite) => {
+            let (sql, selected) = select_attributes(expr, composite, field, depth)?;
+            Ok((sql, PostgresType::Composite(selected)))
+        }
+        _ => Err(ErrorKind::Internal(format!(
+            "{} is not a composite type",
+            pg_type.sql_type_name()
+        ))
+        .into()),
+    }
+}
+
+fn select_attributes(
+    expr: &str,
+    composite: &PostgresComposite,
+    field: &Positioned<Field>,
+    depth: usize,
+) -> Result<(String, PostgresComposite), GraphQLError> {
+    if field.node.selection_set.node.items.is_empty() {
+        return Err(GraphQLError::new(
+            ErrorKind::MissingSelection(field.node.name.node.to_string(), composite.name.clone()),
+            field.pos,
+        ));
+    }
+    let mut pairs = vec![];
+    let mut fields = vec![];
+    for selection in &field.node.selection_set.node.items {
+        if let Selection::Field(child_field) = &selection.node {
+            let child_name = child_field.node.name.node.as_str();
+            let child_key = response_key(&child_field.node);
+            let (value, value_type) = if child_name == "__typename" {
+                (
+                    ["'", &composite.name, "'::text"].concat(),
+                    PostgresType::Str,
+                )
+            } else {
+                let (_, attribute, attribute_type) = composite
+                    .fields
+                    .iter()
+                    .find(|(name, _, _)| name == child_name)
+                    .ok_or_else(|| {
+                        GraphQLError::new(
+                            ErrorKind::UnknownField(composite.name.clone(), child_name.to_string()),
+                            child_field.pos,
+                        )
+                        .in_field(child_key)
+                    })?;
+                let attribute_expr = ["(", expr, ").\"", attribute, "\""].concat();
+                if let PostgresType::Composite(_) = attribute_type.named_type() {
+                    select_composite(&attribute_expr, attribute_type, child_field, depth)
+                        .map_err(|e| e.in_field(child_key))?
+                } else if child_field.node.selection_set.node.items.is_empty() {
+                    (
+                        attribute_type.json_value(&attribute_expr),
+                        attribute_type.clone(),
+                    )
+                } else {
+                    return Err(GraphQLError::new(
+                        ErrorKind::UnexpectedSelection(
+                            child_name.to_string(),
+                            attribute_type.graphql_type().to_string(),
+                        ),
+                        child_field.pos,
+                    )
+                    .in_field(child_key));
+                }
+            };
+            //response keys are GraphQL names, so they never need escaping
+            pairs.push(["'", child_key, "', ", &value].concat());
+            fields.push((child_key.to_string(), child_name.to_string(), value_type));
+        }
+    }
+    //IS NULL would also be true for a value whose attributes are all NULL
+    Ok((
+        [
+            "CASE WHEN ",
+            expr,
+            " IS NOT DISTINCT FROM NULL THEN NULL ELSE json_build_object(",
+            &pairs.join(", "),
+            ") END",
+        ]
+        .concat(),
+        PostgresComposite {
+            fields,
+            ..composite.clone()
+        },
+    ))
+}
@@ -0,0 +1,483 @@
+use crate::build_schema::GraphQLType;
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::order_by::{order_by_terms, OrderTerm};
+use crate::generate_sql::pagination::page_argument;
+use crate::generate_sql::{response_key, GraphQLSchema, JsonBuilderContext, SqlQueryComponents};
+use async_graphql_parser::types::{Field, Selection};
+use async_graphql_parser::Positioned;
+use async_graphql_value::Value;
+use inflector::Inflector;
+use petgraph::prelude::NodeIndex;
+
+//the fields of a connection along with their response keys. The usize of Nodes and
+//EdgeField::Node is the index of the table of the nodes in JsonBuilderContext.table_metadata
+#[derive(Debug)]
+pub enum ConnectionField {
+    Edges(String, Vec<EdgeField>),
+    Nodes(String, usize),
+    PageInfo(String, Vec<PageInfoField>),
+    TotalCount(String),
+    Typename(String, String),
+}
+
+#[derive(Debug)]
+pub enum EdgeField {
+    Cursor(String),
+    Node(String, usize),
+    Typename(String, String),
+}
+
+#[derive(Debug)]
+pub enum PageInfoField {
+    HasNextPage(String),
+    HasPreviousPage(String),
+    StartCursor(String),
+    EndCursor(String),
+    Typename(String, String),
+}
+
+//how a connection is written from the rows of its query. Each row ends with the cursor of its
+//node, followed by the total count, hasNextPage and hasPreviousPage
+#[derive(Debug)]
+pub struct ConnectionMetadata {
+    pub fields: Vec<ConnectionField>,
+    pub cursor_column: usize,
+}
+
+//e.g SiteUsersConnection
+pub fn connection_type_name(node: &GraphQLType) -> String {
+    [&node.type_name().to_plural(), "Connection"].concat()
+}
+
+//e.g SiteUsersEdge
+pub fn edge_type_name(node: &GraphQLType) -> String {
+    [&node.type_name().to_plural(), "Edge"].concat()
+}
+
+//the values (as text) of the order terms of a node, which a cursor is the base64 encoding of as a
+//JSON array. None if the cursor isn't valid
+pub fn decode_cursor(cursor: &str) -> Option<Vec<Option<String>>> {
+    let json = base64::decode(cursor).ok()?;
+    serde_json::from_slice(&json).ok()
+}
+
+impl GraphQLSchema {
+    //a page of the table, which is selected by a subquery that is LEFT JOINed to a row of the
+    //total count and whether there are other pages, so that they are returned for empty pages too.
+    //Nodes are ordered by the orderBy argument followed by the primary keys, and after and before
+    //are the cursors that the page starts after and ends before
+    pub fn build_connection(
+        &self,
+        field: &Positioned<Field>,
+        node_index: NodeIndex<u32>,
+    ) -> Result<JsonBuilderContext, GraphQLError> {
+        let node = &self.g[node_index];
+        let alias = "__table_0__";
+        let mut sql = SqlQueryComponents {
+            selections: String::new(),
+            from: String::new(),
+            filter: String::new(),
+            order_by: String::new(),
+            params: vec![],
+        };
+
+        let condition = self.where_condition(&mut sql, &field.node, node_index, alias)?;
+        let mut order = order_by_terms(node, alias, &field.node)?;
+        order.extend(
+            node.primary_keys
+                .iter()
+                .map(|pk| OrderTerm::primary_key(alias, node, pk)),
+        );
+        let first = page_argument(&field.node, "first")?;
+        let last = page_argument(&field.node, "last")?;
+        if first.is_some() && last.is_some() {
+            return Err(GraphQLError::new(
+                ErrorKind::InvalidValue(String::from(
+                    "A connection can't be paginated by both first and last",
+                )),
+                field.pos,
+            ));
+        }
+        let after = cursor_condition(&mut sql, &field.node, "after", &order)?;
+        let before = cursor_condition(&mut sql, &field.node, "before", &order)?;
+
+        let conditions = condition.iter().cloned().collect::<Vec<String>>();
+        let mut page_conditions = conditions.clone();
+        page_conditions.extend(after.iter().cloned());
+        page_conditions.extend(before.iter().cloned());
+        //the last nodes are selected in reverse, and put back in order by the outer query
+        let (page_order, limit) = match last {
+            Some(last) => (order.iter().map(OrderTerm::reversed).collect(), Some(last)),
+            None => (order.clone(), first),
+        };
+        let rows_of = |conditions: &[String]| {
+            [
+                "SELECT 1 FROM ",
+                &node.sql_name(),
+                " AS ",
+                alias,
+                &where_clause(conditions),
+            ]
+            .concat()
+        };
+        //there are more nodes on one side of the page if the page was limited on that side and
+        //there were more nodes than the limit, or if the page was cut short by a cursor
+        let other_page = |limit: Option<u64>, cursor: &Option<String>| match (limit, cursor) {
+            (Some(limit), _) => format!(
+                "(SELECT count(*) > {} FROM ({} LIMIT {}) AS __page__)",
+                limit,
+                rows_of(&page_conditions),
+                limit + 1
+            ),
+            //the cursor condition is NULL (rather than FALSE) for rows that are NULL in the
+            //columns it compares, so those are outside of it too
+            (None, Some(cursor)) => {
+                let mut outside = conditions.clone();
+                outside.push(["(", cursor, ") IS NOT TRUE"].concat());
+                ["EXISTS (", &rows_of(&outside), ")"].concat()
+            }
+            (None, None) => String::from("FALSE"),
+        };
+        let connection_row = [
+            "(SELECT (SELECT count(*) FROM ",
+            &node.sql_name(),
+            " AS ",
+            alias,
+            &where_clause(&conditions),
+            ") AS total_count, ",
+            &other_page(first, &before),
+            " AS has_next_page, ",
+            &other_page(last, &after),
+            " AS has_previous_page) AS __connection__",
+        ]
+        .concat();
+        let mut page = [
+            "(SELECT * FROM ",
+            &node.sql_name(),
+            " AS ",
+            alias,
+            &where_clause(&page_conditions),
+            " ORDER BY ",
+            &order_sql(&page_order),
+        ]
+        .concat();
+        if let Some(limit) = limit {
+            page.push_str(&format!(" LIMIT {}", limit));
+        }
+        page.push_str(") AS ");
+        page.push_str(alias);
+
+        //the nodes are ordered first, and then their children
+        sql.order_by.push_str(&order_sql(&order));
+        sql.order_by.push_str(", ");
+        let mut table_metadata = vec![];
+        let mut fields = vec![];
+        let mut column_offset = 0;
+        let mut max_id: usize = 0;
+        for selection in &field.node.selection_set.node.items {
+            let child_field = match &selection.node {
+                Selection::Field(child_field) => child_field,
+                _ => continue,
+            };
+            let key = response_key(&child_field.node).to_string();
+            let type_name = connection_type_name(node);
+            match child_field.node.name.node.as_str() {
+                "__typename" => fields.push(ConnectionField::Typename(key, type_name)),
+                "totalCount" => {
+                    leaf(child_field, "Int!")?;
+                    fields.push(ConnectionField::TotalCount(key));
+                }
+                "nodes" => {
+                    let table_index = table_metadata.len();
+                    let (new_offset, new_max_id) = self
+                        .build_selection(
+                            &mut sql,
+                            &mut table_metadata,
+                            child_field,
+                            node_index,
+                            column_offset,
+                            0,
+                            max_id,
+                        )
+                        .map_err(|e| e.in_field(&key))?;
+                    column_offset = new_offset;
+                    max_id = new_max_id;
+                    fields.push(ConnectionField::Nodes(key, table_index));
+                }
+                "edges" => {
+                    let edge_type = edge_type_name(node);
+                    let mut edge_fields = vec![];
+                    for edge_field in selection_fields(child_field, &edge_type)? {
+                        let edge_key = response_key(&edge_field.node).to_string();
+                        match edge_field.node.name.node.as_str() {
+                            "__typename" => {
+                                edge_fields.push(EdgeField::Typename(edge_key, edge_type.clone()))
+                            }
+                            "cursor" => {
+                                leaf(edge_field, "Cursor!").map_err(|e| e.in_field(&key))?;
+                                edge_fields.push(EdgeField::Cursor(edge_key));
+                            }
+                            "node" => {
+                                let table_index = table_metadata.len();
+                                let (new_offset, new_max_id) = self
+                                    .build_selection(
+                                        &mut sql,
+                                        &mut table_metadata,
+                                        edge_field,
+                                        node_index,
+                                        column_offset,
+                                        0,
+                                        max_id,
+                                    )
+                                    .map_err(|e| e.in_field(&edge_key).in_field(&key))?;
+                                column_offset = new_offset;
+                                max_id = new_max_id;
+                                edge_fields.push(EdgeField::Node(edge_key, table_index));
+                            }
+                            other => {
+                                return Err(GraphQLError::new(
+                                    ErrorKind::UnknownField(edge_type, other.to_string()),
+                                    edge_field.pos,
+                                )
+                                .in_field(&edge_key)
+                                .in_field(&key))
+                            }
+                        }
+                    }
+                    fields.push(ConnectionField::Edges(key, edge_fields));
+                }
+                "pageInfo" => {
+                    let mut page_info_fields = vec![];
+                    for info_field in selection_fields(child_field, "PageInfo")? {
+                        let info_key = response_key(&info_field.node).to_string();
+                        let (page_info_field, field_type) = match info_field.node.name.node.as_str()
+                        {
+                            "__typename" => (
+                                PageInfoField::Typename(info_key, String::from("PageInfo")),
+                                "String!",
+                            ),
+                            "hasNextPage" => (PageInfoField::HasNextPage(info_key), "Boolean!"),
+                            "hasPreviousPage" => {
+                                (PageInfoField::HasPreviousPage(info_key), "Boolean!")
+                            }
+                            "startCursor" => (PageInfoField::StartCursor(info_key), "Cursor"),
+                            "endCursor" => (PageInfoField::EndCursor(info_key), "Cursor"),
+                            other => {
+                                return Err(GraphQLError::new(
+                                    ErrorKind::UnknownField(
+                                        String::from("PageInfo"),
+                                        other.to_string(),
+                                    ),
+                                    info_field.pos,
+                                )
+                                .in_field(&info_key)
+                                .in_field(&key))
+                            }
+                        };
+                        leaf(info_field, field_type).map_err(|e| e.in_field(&key))?;
+                        page_info_fields.push(page_info_field);
+                    }
+                    fields.push(ConnectionField::PageInfo(key, page_info_fields));
+                }
+                other => {
+                    return Err(GraphQLError::new(
+                        ErrorKind::UnknownField(type_name, other.to_string()),
+                        child_field.pos,
+                    )
+                    .in_field(&key))
+                }
+            }
+        }
+
+        let cursor = order
+            .iter()
+            .map(|term| [&term.column, "::text"].concat())
+            .collect::<Vec<String>>()
+            .join(", ");
+        //the cursor is NULL if the page is empty, so that no node is written
+        sql.selections.push_str(
+            &[
+                "CASE WHEN ",
+                alias,
+                ".",
+                &node.primary_keys[0],
+                " IS NOT NULL THEN json_build_array(",
+                &cursor,
+                ")::text END AS __cursor__, __connection__.total_count, __connection__.has_next_page, \
+             __connection__.has_previous_page",
+            ]
+            .concat(),
+        );
+        sql.order_by.drain(sql.order_by.len() - 2..);
+        let sql_query = [
+            "SELECT ",
+            &sql.selections,
+            " FROM ",
+            &connection_row,
+            " LEFT JOIN ",
+            &page,
+            " ON TRUE",
+            &sql.from,
+            " ORDER BY ",
+            &sql.order_by,
+        ]
+        .concat();
+
+        Ok(JsonBuilderContext {
+            sql_query,
+            table_metadata,
+            root_key_name: response_key(&field.node).to_owned(),
+            root_query_is_many: false,
+            params: sql.params,
+            value_format: self.value_format,
+            connection: Some(ConnectionMetadata {
+                fields,
+                cursor_column: column_offset,
+            }),
+        })
+    }
+}
+
+//the condition that rows come after (or before, if argument is before) the cursor given as
+//argument. Rows are compared by their first term that differs from the cursor's values
+fn cursor_condition(
+    sql: &mut SqlQueryComponents,
+    field: &Field,
+    argument: &str,
+    order: &[OrderTerm],
+) -> Result<Option<String>, GraphQLError> {
+    let (cursor, pos) = match field.get_argument(argument) {
+        Some(value) => match &value.node {
+            Value::Null => return Ok(None),
+            Value::String(cursor) => (cursor, value.pos),
+            other => {
+                return Err(GraphQLError::new(
+                    ErrorKind::InvalidValue(format!("Expected Cursor, found {}", other)),
+                    value.pos,
+                ))
+            }
+        },
+        None => return Ok(None),
+    };
+    //the values are checked against the types of their terms, so that a malformed cursor isn't
+    //only rejected by the database
+    let values = match decode_cursor(cursor) {
+        Some(values)
+            if values.len() == order.len()
+                && order.iter().zip(&values).all(|(term, value)| match value {
+                    Some(value) => term.pg_type.is_valid_text(value),
+                    None => true,
+                }) =>
+        {
+            values
+        }
+        _ => {
+            return Err(GraphQLError::new(
+                ErrorKind::InvalidValue(format!("Invalid cursor \"{}\"", cursor)),
+                pos,
+            ))
+        }
+    };
+    let after = argument == "after";
+    let placeholders = order
+        .iter()
+        .zip(values)
+        .map(|(term, value)| value.map(|value| sql.push_text(Some(value), term.pg_type.inner())))
+        .collect::<Vec<Option<String>>>();
+
+    let mut alternatives = vec![];
+    for (index, term) in order.iter().enumerate() {
+        let beyond = match beyond(term, placeholders[index].as_deref(), after) {
+            Some(beyond) => beyond,
+            None => continue,
+        };
+        let mut conditions = order[..index]
+            .iter()
+            .zip(&placeholders)
+            .map(|(term, placeholder)| match placeholder {
+                Some(placeholder) => [&term.column, " = ", placeholder].concat(),
+                None => [&term.column, " IS NULL"].concat(),
+            })
+            .collect::<Vec<String>>();
+        conditions.push(beyond);
+        alternatives.push(["(", &conditions.join(" AND "), ")"].concat());
+    }
+    if alternatives.is_empty() {
+        return Ok(Some(String::from("FALSE")));
+    }
+    Ok(Some(["(", &alternatives.join(" OR "), ")"].concat()))
+}
+
+//the condition that the term of a row comes after (or before) value (None if it is NULL). None if
+//no value can
+fn beyond(term: &OrderTerm, value: Option<&str>, after: bool) -> Option<String> {
+    //whether NULLs are on the side of value that is being looked for
+    let nulls_beyond = after != term.nulls_first;
+    match value {
+        Some(value) => {
+            let operator = if after != term.descending {
+                " > "
+            } else {
+                " < "
+            };
+            let comparison = [&term.column, operator, value].concat();
+            Some(if nulls_beyond {
+                ["(", &comparison, " OR ", &term.column, " IS NULL)"].concat()
+            } else {
+                comparison
+            })
+        }
+        None if nulls_beyond => None,
+        None => Some([&term.column, " IS NOT NULL"].concat()),
+    }
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        [" WHERE ", &conditions.join(" AND ")].concat()
+    }
+}
+
+fn order_sql(order: &[OrderTerm]) -> String {
+    order
+        .iter()
+        .map(OrderTerm::sql)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+//the fields selected on an object of the connection, which has to have a selection
+fn selection_fields<'a>(
+    field: &'a Positioned<Field>,
+    type_name: &str,
+) -> Result<Vec<&'a Positioned<Field>>, GraphQLError> {
+    let items = &field.node.selection_set.node.items;
+    if items.is_empty() {
+        return Err(GraphQLError::new(
+            ErrorKind::MissingSelection(field.node.name.node.to_string(), type_name.to_string()),
+            field.pos,
+        )
+        .in_field(response_key(&field.node)));
+    }
+    Ok(items
+        .iter()
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => Some(field),
+            _ => None,
+        })
+        .collect())
+}
+
+//ensures that a scalar field of the connection has no selection
+fn leaf(field: &Positioned<Field>, type_name: &str) -> Result<(), GraphQLError> {
+    if field.node.selection_set.node.items.is_empty() {
+        return Ok(());
+    }
+    Err(GraphQLError::new(
+        ErrorKind::UnexpectedSelection(field.node.name.node.to_string(), type_name.to_string()),
+        field.pos,
+    )
+    .in_field(response_key(&field.node)))
+}
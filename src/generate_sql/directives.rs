@@ -0,0 +1,101 @@
+use crate::error::{ErrorKind, GraphQLError};
+use async_graphql_parser::{
+    types::{Directive, Selection, SelectionSet},
+    Positioned,
+};
+use async_graphql_value::{ConstValue, Name, Value};
+use std::collections::HashMap;
+
+//removes the selections of selection_set (at any depth) that are excluded by @skip or @include,
+//whose conditions can use the values of the operation's variables
+pub fn apply_directives(
+    selection_set: &mut SelectionSet,
+    variables: &HashMap<Name, ConstValue>,
+) -> Result<(), GraphQLError> {
+    let mut items = vec![];
+    for mut selection in selection_set.items.drain(..) {
+        let (directives, child_selection_set) = match &mut selection.node {
+            Selection::Field(field) => {
+                let field = &mut field.node;
+                (&field.directives, Some(&mut field.selection_set.node))
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment = &mut fragment.node;
+                (&fragment.directives, Some(&mut fragment.selection_set.node))
+            }
+            Selection::FragmentSpread(spread) => (&spread.node.directives, None),
+        };
+        if !is_included(directives, variables)? {
+            continue;
+        }
+        if let Some(child_selection_set) = child_selection_set {
+            apply_directives(child_selection_set, variables)?;
+        }
+        items.push(selection);
+    }
+    selection_set.items = items;
+    Ok(())
+}
+
+//a selection is included unless a @skip condition is true or an @include condition is false
+fn is_included(
+    directives: &[Positioned<Directive>],
+    variables: &HashMap<Name, ConstValue>,
+) -> Result<bool, GraphQLError> {
+    for directive in directives {
+        let name = directive.node.name.node.as_str();
+        let condition = match name {
+            "skip" | "include" => condition(directive, variables)?,
+            _ => {
+                return Err(GraphQLError::new(
+                    ErrorKind::Directive(format!("Unknown directive \"@{}\".", name)),
+                    directive.pos,
+                ))
+            }
+        };
+        if condition == (name == "skip") {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+//the value of the if argument of @skip or @include
+fn condition(
+    directive: &Positioned<Directive>,
+    variables: &HashMap<Name, ConstValue>,
+) -> Result<bool, GraphQLError> {
+    let name = &directive.node.name.node;
+    let value = match directive.node.get_argument("if").map(|value| &value.node) {
+        Some(Value::Variable(variable)) => match variables.get(variable) {
+            Some(value) => value.clone(),
+            None => {
+                return Err(GraphQLError::new(
+                    ErrorKind::Variable(format!("Variable \"${}\" is not defined.", variable)),
+                    directive.pos,
+                ))
+            }
+        },
+        Some(value) => value.clone().into_const().unwrap_or(ConstValue::Null),
+        None => {
+            return Err(GraphQLError::new(
+                ErrorKind::Directive(format!(
+                    "Directive \"@{}\" argument \"if\" of type \"Boolean!\" is required, but it \
+                     was not provided.",
+                    name
+                )),
+                directive.pos,
+            ))
+        }
+    };
+    match value {
+        ConstValue::Boolean(condition) => Ok(condition),
+        other => Err(GraphQLError::new(
+            ErrorKind::Directive(format!(
+                "Directive \"@{}\" argument \"if\" expected a value of type \"Boolean!\", found {}.",
+                name, other
+            )),
+            directive.pos,
+        )),
+    }
+}
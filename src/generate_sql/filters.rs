@@ -0,0 +1,369 @@
+use crate::build_schema::PostgresType;
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::{GraphQLSchema, JoinColumns, SqlQueryComponents};
+use async_graphql_parser::types::Field;
+use async_graphql_value::{indexmap::IndexMap, Name, Value};
+use petgraph::prelude::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+
+//what an operator of a filter is given, relative to the type of the column it filters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operand {
+    //a value of the column's type
+    Value,
+    //a list of values of the column's type
+    List,
+    Boolean,
+    //a string that the column (as text) is matched against
+    Pattern,
+}
+
+//every operator of the column filters, in the order they are declared in
+const OPERATORS: [(&str, Operand); 16] = [
+    ("eq", Operand::Value),
+    ("ne", Operand::Value),
+    ("lt", Operand::Value),
+    ("lte", Operand::Value),
+    ("gt", Operand::Value),
+    ("gte", Operand::Value),
+    ("in", Operand::List),
+    ("notIn", Operand::List),
+    ("isNull", Operand::Boolean),
+    ("like", Operand::Pattern),
+    ("ilike", Operand::Pattern),
+    ("startsWith", Operand::Pattern),
+    ("includes", Operand::Pattern),
+    ("contains", Operand::Value),
+    ("containedBy", Operand::Value),
+    ("overlaps", Operand::Value),
+];
+
+//the operators that columns of pg_type can be filtered with. Types that share a GraphQL type (such
+//as text and money, which are both Strings) share a filter, so this only depends on the GraphQL
+//type
+pub fn filter_operators(pg_type: &PostgresType) -> Vec<(&'static str, Operand)> {
+    let supported: &[&str] = match pg_type.inner() {
+        PostgresType::Array(_) => &["eq", "ne", "isNull", "contains", "containedBy", "overlaps"],
+        //xml has no equality operator
+        PostgresType::Xml => &["isNull"],
+        PostgresType::Json | PostgresType::Composite(_) => &["eq", "ne", "isNull"],
+        PostgresType::Boolean => &["eq", "ne", "in", "notIn", "isNull"],
+        other if other.graphql_type_name() == "String" => &[
+            "eq",
+            "ne",
+            "lt",
+            "lte",
+            "gt",
+            "gte",
+            "in",
+            "notIn",
+            "isNull",
+            "like",
+            "ilike",
+            "startsWith",
+            "includes",
+        ],
+        _ => &[
+            "eq", "ne", "lt", "lte", "gt", "gte", "in", "notIn", "isNull",
+        ],
+    };
+    OPERATORS
+        .iter()
+        .filter(|(name, _)| supported.contains(name))
+        .copied()
+        .collect()
+}
+
+impl GraphQLSchema {
+    //the condition of the where argument of field, which selects the table of node_index under
+    //table_alias. None if there is no where argument, or it is empty
+    pub fn where_condition(
+        &self,
+        sql: &mut SqlQueryComponents,
+        field: &Field,
+        node_index: NodeIndex<u32>,
+        table_alias: &str,
+    ) -> Result<Option<String>, GraphQLError> {
+        match field.get_argument("where") {
+            Some(where_node) => match &where_node.node {
+                Value::Object(where_obj) if where_obj.is_empty() => Ok(None),
+                filter => self
+                    .filter_condition(sql, node_index, table_alias, filter, 0)
+                    .map(Some)
+                    .map_err(|e| e.or_at(where_node.pos)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    //the SQL condition of the filter of a table. Every given column filter, relation filter and
+    //combinator has to match. Relations are filtered in subqueries, whose tables are aliased by
+    //their depth
+    fn filter_condition(
+        &self,
+        sql: &mut SqlQueryComponents,
+        node_index: NodeIndex<u32>,
+        table_alias: &str,
+        filter: &Value,
+        depth: usize,
+    ) -> Result<String, GraphQLError> {
+        let filter = expect_object("where", filter)?;
+        let mut conditions = vec![];
+        for (name, value) in filter {
+            let condition = match name.as_str() {
+                "and" | "or" => {
+                    let filters = match value {
+                        Value::List(filters) => filters.iter().collect(),
+                        //a single filter is coerced to a list of it
+                        other => vec![other],
+                    };
+                    let mut combined = vec![];
+                    for filter in filters {
+                        combined.push(self.filter_condition(
+                            sql,
+                            node_index,
+                            table_alias,
+                            filter,
+                            depth,
+                        )?);
+                    }
+                    match name.as_str() {
+                        "and" => join_conditions(combined, " AND ", "TRUE"),
+                        _ => join_conditions(combined, " OR ", "FALSE"),
+                    }
+                }
+                "not" => [
+                    "NOT (",
+                    &self.filter_condition(sql, node_index, table_alias, value, depth)?,
+                    ")",
+                ]
+                .concat(),
+                _ => match self.g[node_index].field_to_types.get(name.as_str()) {
+                    Some((col_name, pg_type)) => column_condition(
+                        sql,
+                        name,
+                        &[table_alias, ".", col_name].concat(),
+                        pg_type,
+                        value,
+                    )?,
+                    None => {
+                        self.relation_condition(sql, node_index, table_alias, name, value, depth)?
+                    }
+                },
+            };
+            conditions.push(condition);
+        }
+        Ok(join_conditions(conditions, " AND ", "TRUE"))
+    }
+
+    //the EXISTS subqueries of a relation filter. Tables referring to this one are filtered by
+    //whether some, every or none of the referring rows match, whilst the table this one refers to
+    //is filtered by whether the referred row matches
+    fn relation_condition(
+        &self,
+        sql: &mut SqlQueryComponents,
+        node_index: NodeIndex<u32>,
+        table_alias: &str,
+        field_name: &str,
+        value: &Value,
+        depth: usize,
+    ) -> Result<String, GraphQLError> {
+        let (other_index, join_cols, is_many) = self
+            .relation(node_index, field_name)
+            .ok_or_else(|| ErrorKind::UnknownArgument(field_name.to_string()))?;
+        let other_alias = ["__filter_", &depth.to_string(), "__"].concat();
+        let join = join_cols
+            .map(|(col, other_col)| {
+                [table_alias, ".", col, " = ", &other_alias, ".", other_col].concat()
+            })
+            .collect::<Vec<String>>()
+            .join(" AND ");
+        let subquery = |condition: &str| {
+            [
+                "EXISTS (SELECT 1 FROM ",
+                &self.g[other_index].sql_name(),
+                " AS ",
+                &other_alias,
+                " WHERE ",
+                &join,
+                " AND ",
+                condition,
+                ")",
+            ]
+            .concat()
+        };
+        if !is_many {
+            let condition =
+                self.filter_condition(sql, other_index, &other_alias, value, depth + 1)?;
+            return Ok(subquery(&condition));
+        }
+        let mut conditions = vec![];
+        for (quantifier, filter) in expect_object(field_name, value)? {
+            let condition =
+                self.filter_condition(sql, other_index, &other_alias, filter, depth + 1)?;
+            conditions.push(match quantifier.as_str() {
+                "some" => subquery(&condition),
+                "none" => ["NOT ", &subquery(&condition)].concat(),
+                //no referring row fails to match
+                "every" => ["NOT ", &subquery(&["NOT (", &condition, ")"].concat())].concat(),
+                _ => return Err(ErrorKind::UnknownArgument(quantifier.to_string()).into()),
+            });
+        }
+        Ok(join_conditions(conditions, " AND ", "TRUE"))
+    }
+
+    //the table that the relation field_name of node_index leads to, the pairs of (column of
+    //node_index, column of the other table) they are joined on, and whether the relation is a list
+    //(the other table refers to this one)
+    fn relation(
+        &self,
+        node_index: NodeIndex<u32>,
+        field_name: &str,
+    ) -> Option<(NodeIndex<u32>, JoinColumns<'_>, bool)> {
+        for edge in self.g.edges_directed(node_index, Direction::Incoming) {
+            let info = edge.weight();
+            if info.graphql_field_name.incoming == field_name {
+                let join_cols = info.outgoing_node_cols.iter();
+                return Some((
+                    edge.source(),
+                    join_cols.zip(info.incoming_node_cols.iter()),
+                    true,
+                ));
+            }
+        }
+        for edge in self.g.edges_directed(node_index, Direction::Outgoing) {
+            let info = edge.weight();
+            if info.graphql_field_name.outgoing == field_name {
+                let join_cols = info.incoming_node_cols.iter();
+                return Some((
+                    edge.target(),
+                    join_cols.zip(info.outgoing_node_cols.iter()),
+                    false,
+                ));
+            }
+        }
+        None
+    }
+}
+
+//the conditions that the operators of a column filter put on the column, which a null filter
+//doesn't constrain. col is the qualified name of the column
+fn column_condition(
+    sql: &mut SqlQueryComponents,
+    field_name: &str,
+    col: &str,
+    pg_type: &PostgresType,
+    value: &Value,
+) -> Result<String, GraphQLError> {
+    let filter = match value {
+        Value::Object(filter) => filter,
+        Value::Null => return Ok(String::from("TRUE")),
+        other => {
+            return Err(ErrorKind::InvalidValue(format!(
+                "Expected a filter for {}, found {}",
+                field_name, other
+            ))
+            .into())
+        }
+    };
+    let operators = filter_operators(pg_type);
+    let mut conditions = vec![];
+    for (operator, operand) in filter {
+        if !operators.iter().any(|(name, _)| operator == name) {
+            return Err(ErrorKind::UnknownArgument(operator.to_string()).into());
+        }
+        //operands are nullable, and null ones don't constrain the column (e.g so that a filter can
+        //be given an optional variable). Nulls are matched by isNull instead
+        if *operand == Value::Null {
+            continue;
+        }
+        conditions.push(operator_condition(
+            sql,
+            operator,
+            col,
+            pg_type.inner(),
+            operand,
+        )?);
+    }
+    Ok(join_conditions(conditions, " AND ", "TRUE"))
+}
+
+fn operator_condition(
+    sql: &mut SqlQueryComponents,
+    operator: &str,
+    col: &str,
+    pg_type: &PostgresType,
+    operand: &Value,
+) -> Result<String, GraphQLError> {
+    let sql_operator = match operator {
+        "eq" => "=",
+        "ne" => "<>",
+        "lt" => "<",
+        "lte" => "<=",
+        "gt" => ">",
+        "gte" => ">=",
+        "contains" => "@>",
+        "containedBy" => "<@",
+        "overlaps" => "&&",
+        "in" | "notIn" => {
+            let list_type =
+                PostgresType::Array(Box::new(PostgresType::Nullable(Box::new(pg_type.clone()))));
+            let placeholder = sql.push_param(operand, &list_type)?;
+            return Ok(match operator {
+                "in" => [col, " = ANY(", &placeholder, ")"].concat(),
+                _ => [col, " <> ALL(", &placeholder, ")"].concat(),
+            });
+        }
+        "isNull" => {
+            return match operand {
+                Value::Boolean(true) => Ok([col, " IS NULL"].concat()),
+                Value::Boolean(false) => Ok([col, " IS NOT NULL"].concat()),
+                other => Err(
+                    ErrorKind::InvalidValue(format!("Expected Boolean, found {}", other)).into(),
+                ),
+            }
+        }
+        _ => {
+            let pattern = sql.push_param(operand, &PostgresType::Str)?;
+            return Ok(match operator {
+                "like" => [col, "::text LIKE ", &pattern].concat(),
+                "ilike" => [col, "::text ILIKE ", &pattern].concat(),
+                "startsWith" => ["starts_with(", col, "::text, ", &pattern, ")"].concat(),
+                _ => ["strpos(", col, "::text, ", &pattern, ") > 0"].concat(),
+            });
+        }
+    };
+    Ok([
+        col,
+        " ",
+        sql_operator,
+        " ",
+        &sql.push_param(operand, pg_type)?,
+    ]
+    .concat())
+}
+
+//joins the conditions with delimiter, bracketing them if there are several. empty is the
+//condition that an empty list of conditions is equivalent to
+fn join_conditions(conditions: Vec<String>, delimiter: &str, empty: &str) -> String {
+    match conditions.len() {
+        0 => empty.to_string(),
+        1 => conditions.into_iter().next().unwrap_or_default(),
+        _ => ["(", &conditions.join(delimiter), ")"].concat(),
+    }
+}
+
+fn expect_object<'a>(
+    argument: &str,
+    value: &'a Value,
+) -> Result<&'a IndexMap<Name, Value>, GraphQLError> {
+    match value {
+        Value::Object(object) => Ok(object),
+        other => Err(ErrorKind::InvalidValue(format!(
+            "Expected an object for {}, found {}",
+            argument, other
+        ))
+        .into()),
+    }
+}
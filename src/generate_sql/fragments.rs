@@ -0,0 +1,287 @@
+use super::response_key;
+use crate::error::{ErrorKind, GraphQLError};
+use crate::schema_model::{SchemaModel, TypeKind};
+use async_graphql_parser::{
+    types::{Field, FragmentDefinition, InlineFragment, Selection, SelectionSet},
+    Positioned,
+};
+use async_graphql_value::{Name, Value};
+use std::collections::HashMap;
+
+//replaces every fragment spread and inline fragment in selection_set (at any depth) by the fields
+//it selects, so that only Selection::Field remains. Fields that end up sharing a response key are
+//merged into one, as the same field may be selected both directly and through a fragment. The
+//typed fragments within the selection of an interface are kept as inline fragments (see
+//select_type), as which of them apply depends on the object returned
+pub fn expand_fragments(
+    selection_set: &mut SelectionSet,
+    fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+    model: &SchemaModel,
+    root_type: &str,
+) -> Result<(), GraphQLError> {
+    inline_fragments(
+        selection_set,
+        fragments,
+        &mut vec![],
+        model,
+        Some(root_type),
+    )?;
+    merge_fields(selection_set)
+}
+
+//the fields of a selection set kept by expand_fragments that apply to an object with one of
+//type_names (its type and the interfaces it implements)
+pub fn select_type(
+    selection_set: &SelectionSet,
+    type_names: &[&str],
+) -> Result<SelectionSet, GraphQLError> {
+    let mut selected = SelectionSet::default();
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::InlineFragment(fragment) => {
+                let applies = match &fragment.node.type_condition {
+                    Some(condition) => type_names.contains(&condition.node.on.node.as_str()),
+                    None => true,
+                };
+                if applies {
+                    selected
+                        .items
+                        .extend(select_type(&fragment.node.selection_set.node, type_names)?.items);
+                }
+            }
+            _ => selected.items.push(selection.clone()),
+        }
+    }
+    merge_fields(&mut selected)?;
+    Ok(selected)
+}
+
+//spread_path holds the names of the fragments currently being expanded, which is used to detect
+//fragments that (directly or indirectly) spread themselves. parent_type is the type whose fields
+//are selected, which is unknown within fields that aren't in the model
+fn inline_fragments(
+    selection_set: &mut SelectionSet,
+    fragments: &HashMap<Name, Positioned<FragmentDefinition>>,
+    spread_path: &mut Vec<Name>,
+    model: &SchemaModel,
+    parent_type: Option<&str>,
+) -> Result<(), GraphQLError> {
+    let keep_typed = parent_type
+        .and_then(|parent_type| model.type_definition(parent_type))
+        .is_some_and(|definition| matches!(definition.kind, TypeKind::Interface(_)));
+    let mut items = vec![];
+    for selection in selection_set.items.drain(..) {
+        let Positioned { pos, node } = selection;
+        match node {
+            Selection::Field(mut field) => {
+                let field_type = parent_type
+                    .and_then(|parent_type| model.field(parent_type, &field.node.name.node))
+                    .map(|field_definition| field_definition.field_type.named_type());
+                inline_fragments(
+                    &mut field.node.selection_set.node,
+                    fragments,
+                    spread_path,
+                    model,
+                    field_type,
+                )?;
+                items.push(Positioned::new(Selection::Field(field), pos));
+            }
+            Selection::InlineFragment(fragment) => {
+                let InlineFragment {
+                    type_condition,
+                    directives,
+                    selection_set: mut fragment_selection_set,
+                } = fragment.node;
+                let fragment_type = match &type_condition {
+                    Some(type_condition) => {
+                        let type_name = &type_condition.node.on.node;
+                        check_spread(model, parent_type, type_name, None)
+                            .map_err(|e| e.or_at(type_condition.pos))?;
+                        Some(type_name.as_str())
+                    }
+                    None => parent_type,
+                };
+                inline_fragments(
+                    &mut fragment_selection_set.node,
+                    fragments,
+                    spread_path,
+                    model,
+                    fragment_type,
+                )?;
+                match type_condition {
+                    Some(type_condition) if keep_typed => items.push(Positioned::new(
+                        Selection::InlineFragment(Positioned::new(
+                            InlineFragment {
+                                type_condition: Some(type_condition),
+                                directives,
+                                selection_set: fragment_selection_set,
+                            },
+                            fragment.pos,
+                        )),
+                        pos,
+                    )),
+                    _ => items.extend(fragment_selection_set.node.items),
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = &spread.node.fragment_name.node;
+                if let Some(start) = spread_path.iter().position(|spread| spread == name) {
+                    let via = &spread_path[start + 1..];
+                    let message = if via.is_empty() {
+                        format!("Cannot spread fragment \"{}\" within itself.", name)
+                    } else {
+                        format!(
+                            "Cannot spread fragment \"{}\" within itself via {}.",
+                            name,
+                            via.iter()
+                                .map(|name| ["\"", name, "\""].concat())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        )
+                    };
+                    return Err(GraphQLError::new(ErrorKind::Fragment(message), pos));
+                }
+                let definition = match fragments.get(name) {
+                    Some(definition) => definition,
+                    None => {
+                        return Err(GraphQLError::new(
+                            ErrorKind::Fragment(format!("Unknown fragment \"{}\".", name)),
+                            pos,
+                        ))
+                    }
+                };
+                let fragment_type = &definition.node.type_condition.node.on.node;
+                check_spread(model, parent_type, fragment_type, Some(name))
+                    .map_err(|e| e.or_at(pos))?;
+                let mut fragment_selection_set = definition.node.selection_set.clone();
+                spread_path.push(name.clone());
+                inline_fragments(
+                    &mut fragment_selection_set.node,
+                    fragments,
+                    spread_path,
+                    model,
+                    Some(fragment_type),
+                )?;
+                spread_path.pop();
+                if keep_typed {
+                    //the spread is kept as the equivalent inline fragment
+                    items.push(Positioned::new(
+                        Selection::InlineFragment(Positioned::new(
+                            InlineFragment {
+                                type_condition: Some(definition.node.type_condition.clone()),
+                                directives: vec![],
+                                selection_set: fragment_selection_set,
+                            },
+                            pos,
+                        )),
+                        pos,
+                    ));
+                } else {
+                    items.extend(fragment_selection_set.node.items);
+                }
+            }
+        }
+    }
+    selection_set.items = items;
+    Ok(())
+}
+
+//a fragment on fragment_type can only be spread within the selection of parent_type if an object
+//can be of both types (e.g a Post fragment can't be spread within a SiteUser). Fragments within
+//fields that aren't in the model (such as those of introspection) aren't checked
+fn check_spread(
+    model: &SchemaModel,
+    parent_type: Option<&str>,
+    fragment_type: &str,
+    fragment_name: Option<&Name>,
+) -> Result<(), GraphQLError> {
+    let parent_type = match parent_type {
+        Some(parent_type) => parent_type,
+        None => return Ok(()),
+    };
+    let fragment_types = model.possible_types(fragment_type);
+    if fragment_types.is_empty() {
+        return Err(ErrorKind::Fragment(format!("Unknown type \"{}\".", fragment_type)).into());
+    }
+    if model
+        .possible_types(parent_type)
+        .iter()
+        .any(|possible_type| fragment_types.contains(possible_type))
+    {
+        return Ok(());
+    }
+    let fragment = match fragment_name {
+        Some(name) => format!("Fragment \"{}\"", name),
+        None => String::from("Fragment"),
+    };
+    Err(ErrorKind::Fragment(format!(
+        "{} cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+        fragment, parent_type, fragment_type
+    ))
+    .into())
+}
+
+//fields sharing a response key have to select the same field with the same arguments, so that
+//they can be merged into one
+fn merge_fields(selection_set: &mut SelectionSet) -> Result<(), GraphQLError> {
+    let mut items: Vec<Positioned<Selection>> = vec![];
+    for selection in selection_set.items.drain(..) {
+        if let Selection::Field(field) = &selection.node {
+            let key = response_key(&field.node);
+            let existing = items.iter_mut().find_map(|item| match &mut item.node {
+                Selection::Field(existing) if response_key(&existing.node) == key => Some(existing),
+                _ => None,
+            });
+            if let Some(existing) = existing {
+                if existing.node.name.node != field.node.name.node {
+                    let reason = format!(
+                        "\"{}\" and \"{}\" are different fields",
+                        existing.node.name.node, field.node.name.node
+                    );
+                    return Err(GraphQLError::new(
+                        ErrorKind::FieldsConflict(key.to_string(), reason),
+                        selection.pos,
+                    ));
+                }
+                if sorted_arguments(&existing.node) != sorted_arguments(&field.node) {
+                    return Err(GraphQLError::new(
+                        ErrorKind::FieldsConflict(
+                            key.to_string(),
+                            String::from("they have differing arguments"),
+                        ),
+                        selection.pos,
+                    ));
+                }
+                existing
+                    .node
+                    .selection_set
+                    .node
+                    .items
+                    .extend(field.node.selection_set.node.items.iter().cloned());
+                continue;
+            }
+        }
+        items.push(selection);
+    }
+    for item in &mut items {
+        match &mut item.node {
+            Selection::Field(field) => merge_fields(&mut field.node.selection_set.node)?,
+            Selection::InlineFragment(fragment) => {
+                merge_fields(&mut fragment.node.selection_set.node)?
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+    selection_set.items = items;
+    Ok(())
+}
+
+fn sorted_arguments(field: &Field) -> Vec<(&Name, &Value)> {
+    let mut arguments = field
+        .arguments
+        .iter()
+        .map(|(name, value)| (&name.node, &value.node))
+        .collect::<Vec<(&Name, &Value)>>();
+    arguments.sort_by(|a, b| a.0.cmp(b.0));
+    arguments
+}
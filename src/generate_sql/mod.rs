@@ -1,25 +1,58 @@
+mod arguments;
 mod component_builder;
+mod composites;
+mod connections;
+mod directives;
+mod filters;
+mod fragments;
+mod node_id;
+mod order_by;
+mod pagination;
+mod param_converter;
 #[cfg(test)]
 #[path = "./test.rs"]
 mod test;
+mod variables;
 use std::iter::Zip;
 use std::slice::Iter;
 
 use crate::build_schema::{GraphQLEdgeInfo, GraphQLType, Operation, PostgresType};
+use crate::error::{ErrorKind, GraphQLError};
+use crate::schema_model::{Introspection, SchemaModel};
+use crate::state_machine_builder::ValueFormat;
 use async_graphql_parser::{
     parse_query,
-    types::{DocumentOperations, Selection, SelectionSet},
+    types::{DocumentOperations, Field, OperationType, Selection, SelectionSet},
     Positioned,
 };
-use convert_case::{Case, Casing};
-use inflector::Inflector;
+use async_graphql_value::Value;
+use composites::select_composite;
+pub use connections::{
+    connection_type_name, edge_type_name, ConnectionField, ConnectionMetadata, EdgeField,
+    PageInfoField,
+};
+pub use filters::{filter_operators, Operand};
+use node_id::select_node_id;
+use order_by::order_by_terms;
+pub use order_by::{order_by_type_name, order_by_values};
+use pagination::Page;
 use petgraph::{graph::DiGraph, prelude::NodeIndex};
+use serde_json::{Map, Value as JsonValue};
 use std::collections::HashMap;
+use tokio_postgres::types::ToSql;
+
+//pairs of (parent column, child column) that a join is performed on
+type JoinColumns<'a> = Zip<Iter<'a, String>, Iter<'a, String>>;
 
 #[derive(Clone)]
 pub struct GraphQLSchema {
     pub g: DiGraph<GraphQLType, GraphQLEdgeInfo>,
     pub field_to_operation: HashMap<String, Operation>,
+    pub value_format: ValueFormat,
+    //the GraphQL types of the schema, which variables are checked against and introspection
+    //and the SDL are answered from
+    pub schema_model: SchemaModel,
+    pub introspection: Introspection,
 }
 #[derive(Debug)]
 pub struct JsonBuilderContext {
@@ -27,311 +60,594 @@ pub struct JsonBuilderContext {
     pub table_metadata: Vec<TableMetadata>,
     pub root_key_name: String,
     pub root_query_is_many: bool,
+    //the values of the $1..$n placeholders in sql_query, as text (None is NULL)
+    pub params: Vec<Option<String>>,
+    pub value_format: ValueFormat,
+    //how to write the root field if it is a connection, rather than a list or object
+    pub connection: Option<ConnectionMetadata>,
+}
+//a root field of the operation, along with how its value is produced
+#[derive(Debug)]
+pub enum RootField {
+    Sql(JsonBuilderContext),
+    //a meta field, whose value is already known (its response key and value)
+    Introspection(String, JsonValue),
 }
 pub struct SqlQueryComponents {
     selections: String,
     from: String,
     filter: String,
     order_by: String,
+    params: Vec<Option<String>>,
 }
 #[derive(Debug)]
 pub struct TableMetadata {
     pub graphql_fields: Vec<ColumnInfo>,
     pub primary_key_range: std::ops::Range<usize>,
 }
+//the usize of the foreign variants is the index of the child table in
+//JsonBuilderContext.table_metadata
 #[derive(Debug)]
 pub enum ColumnInfo {
-    Foreign(String),
-    ForeignSingular(String),
+    Foreign(String, usize),
+    ForeignSingular(String, usize),
     Terminal(String, PostgresType),
+    //__typename, along with the name of the type
+    Typename(String, String),
+    //the nodeId of the object, which is selected as the JSON array that is base64 encoded
+    NodeId(String),
 }
 
-impl GraphQLSchema {
-    pub fn new(
-        g: DiGraph<GraphQLType, GraphQLEdgeInfo>,
-        field_to_operation: HashMap<String, Operation>,
-    ) -> GraphQLSchema {
-        GraphQLSchema {
-            g,
-            field_to_operation,
+impl JsonBuilderContext {
+    //the parameters in the form expected by tokio_postgres' query methods
+    pub fn sql_params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.params
+            .iter()
+            .map(|param| param as &(dyn ToSql + Sync))
+            .collect()
+    }
+}
+
+impl SqlQueryComponents {
+    //binds value as the next query parameter and returns the placeholder referring to it, cast to
+    //the type of the column it is compared with or assigned to
+    fn push_param(
+        &mut self,
+        value: &Value,
+        pg_type: &PostgresType,
+    ) -> Result<String, GraphQLError> {
+        let text = pg_type.stringify_param(value)?;
+        Ok(self.push_text(text, pg_type))
+    }
+
+    //binds text that Postgres casts to pg_type, without it being validated as a GraphQL value
+    fn push_text(&mut self, text: Option<String>, pg_type: &PostgresType) -> String {
+        self.params.push(text);
+        format!("${}::text::{}", self.params.len(), pg_type.sql_type_name())
+    }
+}
+
+impl ColumnInfo {
+    fn set_table_index(&mut self, table_index: usize) {
+        match self {
+            ColumnInfo::Foreign(_, index) | ColumnInfo::ForeignSingular(_, index) => {
+                *index = table_index
+            }
+            ColumnInfo::Terminal(_, _) | ColumnInfo::Typename(_, _) | ColumnInfo::NodeId(_) => {}
         }
     }
+}
 
-    pub fn parse_graphql(&self, query: &str) -> Result<JsonBuilderContext, String> {
-        let ast;
-        match parse_query::<&str>(query) {
-            Ok(tree) => ast = tree,
-            Err(e) => return Err(e.to_string()),
+impl GraphQLSchema {
+    pub fn parse_graphql(
+        &self,
+        query: &str,
+        variables: &Map<String, JsonValue>,
+        operation_name: Option<&str>,
+    ) -> Result<Vec<RootField>, GraphQLError> {
+        let ast = parse_query::<&str>(query)?;
+        //an anonymous operation has to be the only one in the document, so it can't be named
+        let mut operation =
+            match (ast.operations, operation_name) {
+                (DocumentOperations::Single(operation), None) => operation.node,
+                (DocumentOperations::Multiple(mut operation_map), Some(name)) => {
+                    match operation_map.remove(name) {
+                        Some(operation) => operation.node,
+                        None => return Err(unknown_operation(name)),
+                    }
+                }
+                (DocumentOperations::Multiple(operation_map), None) => {
+                    match (operation_map.len(), operation_map.into_values().next()) {
+                        (1, Some(operation)) => operation.node,
+                        _ => return Err(ErrorKind::Operation(String::from(
+                            "Must provide operation name if query contains multiple operations.",
+                        ))
+                        .into()),
+                    }
+                }
+                (DocumentOperations::Single(_), Some(name)) => return Err(unknown_operation(name)),
+            };
+        let variables =
+            variables::resolve_variables(self, &operation.variable_definitions, variables)?;
+        //skipped selections are removed before fragments are expanded, as the directives of
+        //fragments are lost by expanding them
+        let mut fragment_definitions = ast.fragments;
+        directives::apply_directives(&mut operation.selection_set.node, &variables)?;
+        for definition in fragment_definitions.values_mut() {
+            directives::apply_directives(&mut definition.node.selection_set.node, &variables)?;
         }
-        match ast.operations {
-            DocumentOperations::Single(Positioned { pos: _, node }) => {
-                self.visit_query(&node.selection_set)
-            }
-            DocumentOperations::Multiple(operation_map) => {
-                self.visit_query(&operation_map.values().next().unwrap().node.selection_set)
-            }
+        let root_type = match operation.ty {
+            OperationType::Mutation => "Mutation",
+            _ => "Query",
+        };
+        fragments::expand_fragments(
+            &mut operation.selection_set.node,
+            &fragment_definitions,
+            &self.schema_model,
+            root_type,
+        )?;
+        arguments::check_arguments(&self.schema_model, &operation.selection_set.node, root_type)?;
+        if !operation.variable_definitions.is_empty() {
+            variables::check_variable_usages(
+                &self.schema_model,
+                &operation.variable_definitions,
+                &operation.selection_set.node,
+                root_type,
+            )?;
         }
+        variables::substitute_variables(&mut operation.selection_set.node, &variables)?;
+        self.visit_query(&operation.selection_set, operation.ty)
     }
+    //every root field is resolved by its own SQL query, apart from the introspection meta fields
+    //which are answered directly from the schema
     fn visit_query(
         &self,
         selection_set: &Positioned<SelectionSet>,
-    ) -> Result<JsonBuilderContext, String> {
+        operation_type: OperationType,
+    ) -> Result<Vec<RootField>, GraphQLError> {
+        let mut root_fields = vec![];
+        //fragments were expanded, so only fields are left
+        for selection in &selection_set.node.items {
+            if let Selection::Field(field) = &selection.node {
+                let key = response_key(&field.node).to_owned();
+                let root_field = self
+                    .visit_root_field(field, key.to_string(), operation_type)
+                    .map_err(|e| e.or_at(field.pos).in_field(&key))?;
+                root_fields.push(root_field);
+            }
+        }
+        Ok(root_fields)
+    }
+
+    fn visit_root_field(
+        &self,
+        field: &Positioned<Field>,
+        key: String,
+        operation_type: OperationType,
+    ) -> Result<RootField, GraphQLError> {
+        Ok(match (field.node.name.node.as_str(), operation_type) {
+            ("__typename", OperationType::Mutation) => {
+                RootField::Introspection(key, JsonValue::from("Mutation"))
+            }
+            ("__typename", _) => RootField::Introspection(key, JsonValue::from("Query")),
+            ("__schema", OperationType::Query) => {
+                RootField::Introspection(key, self.introspection.resolve_schema(&field.node)?)
+            }
+            ("__type", OperationType::Query) => {
+                RootField::Introspection(key, self.introspection.resolve_type_by_name(&field.node)?)
+            }
+            _ => RootField::Sql(self.build_root_query(field)?),
+        })
+    }
+
+    fn build_root_query(
+        &self,
+        field: &Positioned<Field>,
+    ) -> Result<JsonBuilderContext, GraphQLError> {
+        let field_name = field.node.name.node.as_str();
+
+        //clone operation (or if invalid throw error)
+        let operation = match self.field_to_operation.get(field_name) {
+            Some(op) => op.clone(),
+            None => return Err(ErrorKind::UnknownRootField(field_name.to_string()).into()),
+        };
+        match operation {
+            Operation::Connection(node_index) => self.build_connection(field, node_index),
+            Operation::Node => self.build_node(field),
+            operation => self.build_table_query(field, operation),
+        }
+    }
+
+    //the query of a root field that selects, deletes, updates or inserts rows of a single table
+    fn build_table_query(
+        &self,
+        field: &Positioned<Field>,
+        operation: Operation,
+    ) -> Result<JsonBuilderContext, GraphQLError> {
         let mut sql = SqlQueryComponents {
             selections: String::new(),
             from: String::new(),
             filter: String::from(" WHERE "),
             order_by: String::new(),
+            params: vec![],
         };
         let mut table_metadata: Vec<TableMetadata> = vec![];
 
-        let root_key_name: &str;
-        if let Selection::Field(field) = &selection_set.node.items.get(0).unwrap().node {
-            root_key_name = field.node.name.node.as_str();
+        //we want to extract include_filter (whether we should build the where a = b clause)
+        //we need this as we need to know whether we are expecting arguments (and to throw an
+        //error if we don't receive them)
 
-            //clone operation (or if invalid throw error)
-            let operation;
-            match self.field_to_operation.get(root_key_name) {
-                Some(op) => operation = op.clone(),
-                None => return Err(format!("No operation named \"{}\"", root_key_name)),
+        //extract node index sql.from the operation (this can be used to perform shared logic
+        //between operations)
+        let (is_many, node_index) = match operation {
+            Operation::Query(is_many, node_index) => (is_many, node_index),
+            Operation::Delete(node_index) => (false, node_index),
+            Operation::Update(node_index) => (false, node_index),
+            Operation::QueryByUnique(node_index, _)
+            | Operation::DeleteByUnique(node_index, _)
+            | Operation::UpdateByUnique(node_index, _) => (false, node_index),
+            Operation::Insert(node_index) => (false, node_index),
+            Operation::Connection(_) | Operation::Node => {
+                unreachable!("Connections and nodes are built separately")
             }
-
-            //we want to extract include_filter (whether we should build the where a = b clause)
-            //we need this as we need to know whether we are expecting arguments (and to throw an
-            //error if we don't receive them)
-
-            //extract node index sql.from the operation (this can be used to perform shared logic
-            //between operations)
-            let (is_many, node_index) = match operation {
-                Operation::Query(is_many, node_index) => (is_many, node_index),
-                Operation::Delete(node_index) => (false, node_index),
-                Operation::Update(node_index) => (false, node_index),
-                Operation::Insert(node_index) => (false, node_index),
-            };
-            if let Err(e) = &self.build_selection(
-                &mut sql,
-                &mut table_metadata,
-                selection_set.node.items.get(0).unwrap(),
-                node_index,
-                0,
-                0,
-            ) {
-                return Err(e.to_string());
+        };
+        //only lists can be ordered and paginated
+        for argument in ["orderBy", "first", "offset"] {
+            if let Some(value) = field.node.get_argument(argument) {
+                if !is_many {
+                    return Err(GraphQLError::new(
+                        ErrorKind::UnknownArgument(argument.to_string()),
+                        value.pos,
+                    ));
+                }
             }
+        }
+        self.build_selection(&mut sql, &mut table_metadata, field, node_index, 0, 0, 0)?;
 
-            if !is_many {
-                match &selection_set.node.items.get(0).unwrap().node {
-                    Selection::Field(Positioned { pos: _, node }) => {
-                        //if the value of the first (or only) primary key was provided, we can assume
-                        //that we can build a where clause for all (or one) primay keys
-                        for pk in &self.g[node_index].primary_keys {
-                            match node.get_argument(&pk.to_camel_case()) {
-                                Some(pk_val) => sql
-                                    .filter
-                                    .push_str(&format!("__table_0__.{} = {} and ", pk, pk_val)),
-                                None => return Err(format!("Expected input field {}", pk)),
-                            }
-                        }
-                        sql.filter.drain(sql.filter.len() - 4..sql.filter.len());
-                    }
-                    _ => panic!("Didn't get Selection::Field"),
-                }
+        //inserts don't filter, so their primary key arguments are values to insert rather than
+        //a WHERE clause (binding them here too would leave unused parameters)
+        let key_condition = match operation {
+            Operation::Query(true, _) | Operation::Insert(_) => None,
+            Operation::QueryByUnique(_, key)
+            | Operation::DeleteByUnique(_, key)
+            | Operation::UpdateByUnique(_, key) => {
+                Some(self.unique_key_condition(&mut sql, &field.node, node_index, key)?)
             }
+            _ => Some(self.primary_key_condition(&mut sql, &field.node, node_index)?),
+        };
+        if let Some(condition) = key_condition {
+            sql.filter.push_str(&condition);
+            sql.filter.push(' ');
+        }
 
-            //remove trailing comma sql.from select
+        //remove trailing comma sql.from select
+        if sql.selections.ends_with(", ") {
             sql.selections
                 .drain(sql.selections.len() - 2..sql.selections.len());
+        }
 
-            let sql_query;
-            match operation {
-                Operation::Query(root_query_is_many, _) => {
-                    match component_builder::select(
-                        &mut sql,
-                        &self.g[node_index].table_name,
-                        root_query_is_many,
-                        selection_set,
-                        &self.g[node_index].field_to_types,
-                    ) {
-                        Ok(val) => sql_query = val,
-                        Err(e) => return Err(e),
+        let table_name = &self.g[node_index].sql_name();
+        let field_to_types = &self.g[node_index].field_to_types;
+        let sql_query = match operation {
+            Operation::Query(_, _) | Operation::QueryByUnique(_, _) => {
+                let condition =
+                    self.where_condition(&mut sql, &field.node, node_index, "__table_0__")?;
+                //a page of the table is selected by a subquery, which is filtered instead
+                match Page::new(&field.node)? {
+                    Some(page) => {
+                        let node = &self.g[node_index];
+                        let order = order_by_terms(node, "__table_0__", &field.node)?;
+                        let conditions = condition.into_iter().collect::<Vec<String>>();
+                        let table = page.subquery(node, "__table_0__", &conditions, &order);
+                        component_builder::select(&mut sql, &table, is_many, None)?
                     }
-                }
-                Operation::Delete(_) => {
-                    sql_query = component_builder::delete(&mut sql, &self.g[node_index].table_name);
-                }
-                Operation::Update(_) => {
-                    match component_builder::update(
+                    None => component_builder::select(
                         &mut sql,
-                        &self.g[node_index].table_name,
-                        selection_set,
-                        &self.g[node_index].field_to_types,
-                    ) {
-                        Ok(val) => sql_query = val,
-                        Err(e) => return Err(e),
-                    }
+                        &[table_name, " AS __table_0__"].concat(),
+                        is_many,
+                        condition,
+                    )?,
                 }
-                Operation::Insert(_) => {
-                    match component_builder::insert(
-                        &mut sql,
-                        &self.g[node_index].table_name,
-                        selection_set,
-                        &self.g[node_index].field_to_types,
-                    ) {
-                        Ok(val) => sql_query = val,
-                        Err(e) => return Err(e),
-                    }
-                }
-            };
+            }
+            Operation::Delete(_) | Operation::DeleteByUnique(_, _) => {
+                component_builder::delete(&mut sql, table_name)
+            }
+            Operation::Update(_) | Operation::UpdateByUnique(_, _) => {
+                component_builder::update(&mut sql, table_name, &field.node, field_to_types)?
+            }
+            Operation::Insert(_) => {
+                component_builder::insert(&mut sql, table_name, &field.node, field_to_types)?
+            }
+            Operation::Connection(_) | Operation::Node => {
+                unreachable!("Connections and nodes are built separately")
+            }
+        };
 
-            Ok(JsonBuilderContext {
-                sql_query,
-                table_metadata,
-                root_key_name: root_key_name.to_owned(),
-                root_query_is_many: is_many,
-            })
-        } else {
-            panic!("First selection_set item isn't a field");
+        Ok(JsonBuilderContext {
+            sql_query,
+            table_metadata,
+            root_key_name: response_key(&field.node).to_owned(),
+            root_query_is_many: is_many,
+            params: sql.params,
+            value_format: self.value_format,
+            connection: None,
+        })
+    }
+
+    //the condition selecting the row (of the table aliased __table_0__) whose columns of the
+    //unique constraint at index key are given as the arguments of field
+    fn unique_key_condition(
+        &self,
+        sql: &mut SqlQueryComponents,
+        field: &Field,
+        node_index: NodeIndex<u32>,
+        key: usize,
+    ) -> Result<String, GraphQLError> {
+        let node = &self.g[node_index];
+        let mut conditions = vec![];
+        for col in &node.unique_constraints[key] {
+            let name = node.field_name(col);
+            let pg_type = match node.field_to_types.get(&name) {
+                Some((_, pg_type)) => pg_type,
+                None => return Err(ErrorKind::Internal(format!("No column {}", col)).into()),
+            };
+            let value = field
+                .get_argument(&name)
+                .ok_or_else(|| ErrorKind::MissingArgument(name.to_string()))?;
+            //NULLs are never equal, so the columns are required even if they are nullable
+            let placeholder = sql
+                .push_param(&value.node, pg_type.inner())
+                .map_err(|e| e.or_at(value.pos))?;
+            conditions.push(["__table_0__.", col, " = ", &placeholder].concat());
         }
+        Ok(conditions.join(" AND "))
     }
 
+    //writes the selections and joins of this table (whose alias is __table_{table_id}__) and
+    //recursively of all its children. max_id is the largest table id handed out so far, so that
+    //sibling and nested joins never share an alias. Returns the column offset after this table
+    //and its children, along with the new largest table id
+    #[allow(clippy::too_many_arguments)]
     fn build_selection(
         &self,
         sql: &mut SqlQueryComponents,
         table_metadata: &mut Vec<TableMetadata>,
-        selection: &Positioned<Selection>,
+        field: &Positioned<Field>,
         node_index: NodeIndex<u32>,
         column_offset: usize,
-        mut local_id: u8,
-    ) -> Result<(usize, u8), String> {
-        let SqlQueryComponents {
-            from,
-            selections,
-            filter: _,
-            order_by,
-        } = sql;
-        let mut new_col_offset = column_offset;
-        if let Selection::Field(field) = &selection.node {
-            //first we recursively get all queries from the children
-            //this field is terminal
-            let id_copy = local_id;
-            let current_alias = GraphQLSchema::table_alias(local_id);
-            let mut children: Vec<(&Positioned<Selection>, NodeIndex<u32>)> = vec![];
-
-            //we need to add all primary keys of this particular table (so we know how to group
-            //separate objects)
-            for (i, pk) in self.g[node_index].primary_keys.iter().enumerate() {
-                selections.push_str(&current_alias);
-                selections.push('.');
-                selections.push_str(pk);
-                selections.push_str(" AS");
-                selections.push_str(" __t");
-                selections.push_str(&id_copy.to_string());
-                selections.push_str("_pk");
-                selections.push_str(&i.to_string());
-                selections.push_str("__, ");
-            }
+        table_id: usize,
+        mut max_id: usize,
+    ) -> Result<(usize, usize), GraphQLError> {
+        if field.node.selection_set.node.items.is_empty() {
+            return Err(GraphQLError::new(
+                ErrorKind::MissingSelection(
+                    field.node.name.node.to_string(),
+                    self.g[node_index].type_name(),
+                ),
+                field.pos,
+            ));
+        }
+        let primary_keys = &self.g[node_index].primary_keys;
+        //primary keys are selected first, so the columns start after them
+        let mut new_col_offset = column_offset + primary_keys.len();
+        let current_alias = GraphQLSchema::table_alias(table_id);
 
-            let mut encountered_join = false;
-            let mut graphql_fields: Vec<ColumnInfo> = vec![];
-            for selection in &field.node.selection_set.node.items {
-                if let Selection::Field(child_field) = &selection.node {
-                    let child_name = child_field.node.name.node.as_str();
-                    match self.g[node_index].field_to_types.get(child_name) {
-                        Some(column_info) => {
-                            let column_name = &[&current_alias, ".", &column_info.0].concat();
-                            graphql_fields
-                                .push(ColumnInfo::Terminal(child_name.to_string(), column_info.1));
-                            selections.push_str(column_name);
-                            selections.push_str(" AS __t");
-                            selections.push_str(&id_copy.to_string());
-                            selections.push_str("_c");
-                            selections.push_str(&(new_col_offset - column_offset).to_string());
-                            selections.push_str("__, ");
-                            new_col_offset += 1;
-                        }
-                        None => {
-                            //if we have a child join then we need to order the parent by its primary
-                            //key to allow us to capture all children for the parent when iterating
-                            if !encountered_join {
-                                encountered_join = true;
-                                for pk in &self.g[node_index].primary_keys {
-                                    order_by.push_str(&current_alias);
-                                    order_by.push('.');
-                                    order_by.push_str(pk);
-                                    order_by.push_str(", ");
-                                }
+        //(field, node of the child table, alias id of the child table, index of the child in
+        //graphql_fields)
+        let mut children: Vec<(&Positioned<Field>, NodeIndex<u32>, usize, usize)> = vec![];
+
+        //we need to add all primary keys of this particular table (so we know how to group
+        //separate objects)
+        for (i, pk) in primary_keys.iter().enumerate() {
+            sql.selections.push_str(&current_alias);
+            sql.selections.push('.');
+            sql.selections.push_str(pk);
+            sql.selections.push_str(" AS");
+            sql.selections.push_str(" __t");
+            sql.selections.push_str(&table_id.to_string());
+            sql.selections.push_str("_pk");
+            sql.selections.push_str(&i.to_string());
+            sql.selections.push_str("__, ");
+        }
+
+        //the table is ordered by its orderBy argument ahead of its primary keys (and the order of
+        //its children), so that rows are still grouped under the right parent
+        for term in order_by_terms(&self.g[node_index], &current_alias, &field.node)? {
+            sql.order_by.push_str(&term.sql());
+            sql.order_by.push_str(", ");
+        }
+        //pages are ordered by their primary keys too, as they are selected in that order
+        let mut ordered_by_pk = Page::new(&field.node)?.is_some();
+        if ordered_by_pk {
+            for pk in primary_keys {
+                sql.order_by
+                    .push_str(&[&current_alias, ".", pk, ", "].concat());
+            }
+        }
+        let mut graphql_fields: Vec<ColumnInfo> = vec![];
+        let mut terminal_count = 0;
+        for selection in &field.node.selection_set.node.items {
+            if let Selection::Field(child_field) = &selection.node {
+                let child_name = child_field.node.name.node.as_str();
+                let child_key = response_key(&child_field.node);
+                if child_name == "__typename" {
+                    graphql_fields.push(ColumnInfo::Typename(
+                        child_key.to_string(),
+                        self.g[node_index].type_name(),
+                    ));
+                    continue;
+                }
+                if child_name == "nodeId" && !primary_keys.is_empty() {
+                    if !child_field.node.selection_set.node.items.is_empty() {
+                        return Err(GraphQLError::new(
+                            ErrorKind::UnexpectedSelection(
+                                child_name.to_string(),
+                                String::from("ID!"),
+                            ),
+                            child_field.pos,
+                        )
+                        .in_field(child_key));
+                    }
+                    graphql_fields.push(ColumnInfo::NodeId(child_key.to_string()));
+                    sql.selections.push_str(
+                        &[
+                            &select_node_id(&self.g[node_index], &current_alias),
+                            " AS __t",
+                            &table_id.to_string(),
+                            "_c",
+                            &terminal_count.to_string(),
+                            "__, ",
+                        ]
+                        .concat(),
+                    );
+                    terminal_count += 1;
+                    new_col_offset += 1;
+                    continue;
+                }
+                match self.g[node_index].field_to_types.get(child_name) {
+                    Some(column_info) => {
+                        let column = [&current_alias, ".", &column_info.0].concat();
+                        let (column_name, pg_type) =
+                            if let PostgresType::Composite(_) = column_info.1.named_type() {
+                                select_composite(&column, &column_info.1, child_field, 0)
+                                    .map_err(|e| e.in_field(child_key))?
+                            } else if child_field.node.selection_set.node.items.is_empty() {
+                                (column_info.1.select_column(&column), column_info.1.clone())
+                            } else {
+                                return Err(GraphQLError::new(
+                                    ErrorKind::UnexpectedSelection(
+                                        child_name.to_string(),
+                                        column_info.1.graphql_type().to_string(),
+                                    ),
+                                    child_field.pos,
+                                )
+                                .in_field(child_key));
+                            };
+                        graphql_fields.push(ColumnInfo::Terminal(child_key.to_string(), pg_type));
+                        sql.selections.push_str(&column_name);
+                        sql.selections.push_str(" AS __t");
+                        sql.selections.push_str(&table_id.to_string());
+                        sql.selections.push_str("_c");
+                        sql.selections.push_str(&terminal_count.to_string());
+                        sql.selections.push_str("__, ");
+                        terminal_count += 1;
+                        new_col_offset += 1;
+                    }
+                    None => {
+                        //if we have a child join then we need to order the parent by its primary
+                        //key to allow us to capture all children for the parent when iterating
+                        if !ordered_by_pk {
+                            ordered_by_pk = true;
+                            for pk in primary_keys {
+                                sql.order_by.push_str(&current_alias);
+                                sql.order_by.push('.');
+                                sql.order_by.push_str(pk);
+                                sql.order_by.push_str(", ");
                             }
-                            local_id += 1;
-                            let child_alias = GraphQLSchema::table_alias(local_id);
+                        }
+                        max_id += 1;
+                        let child_alias = GraphQLSchema::table_alias(max_id);
 
-                            let join_cols: Zip<Iter<String>, Iter<String>>;
-                            let child_node_index: NodeIndex<u32>;
-                            match self.find_edge_and_endpoints(
+                        let (join_cols, child_node_index) = self
+                            .find_edge_and_endpoints(
                                 node_index,
                                 child_name,
+                                child_key,
                                 &mut graphql_fields,
-                            ) {
-                                Ok((j_c, node)) => {
-                                    join_cols = j_c;
-                                    child_node_index = node;
+                            )
+                            .map_err(|e| e.or_at(child_field.pos).in_field(child_key))?;
+
+                        //if its not terminal, this field must be some foreign field. Search the nodes
+                        //edges for the edge that corresponds to this graphql field, and whether its a
+                        //one to many or many to one relation
+                        let mut conditions = join_cols
+                            .map(|(col1, col2)| {
+                                [&current_alias, ".", col1, " = ", &child_alias, ".", col2].concat()
+                            })
+                            .collect::<Vec<String>>();
+                        //lists of children can be filtered (which only filters the joined children
+                        //rather than their parent), ordered and paginated
+                        for argument in ["where", "orderBy", "first", "offset"] {
+                            if let Some(value) = child_field.node.get_argument(argument) {
+                                if !matches!(graphql_fields.last(), Some(ColumnInfo::Foreign(_, _)))
+                                {
+                                    return Err(GraphQLError::new(
+                                        ErrorKind::UnknownArgument(argument.to_string()),
+                                        value.pos,
+                                    )
+                                    .in_field(child_key));
                                 }
-                                Err(e) => return Err(e),
                             }
-
-                            from.push_str(" LEFT JOIN ");
-                            from.push_str(&self.g[child_node_index].table_name);
-                            from.push_str(" AS ");
-                            from.push_str(&child_alias);
-                            from.push_str(" ON ");
-
-                            //if its not terminal, this field must be some foreign field. Search the nodes
-                            //edges for the edge that corresponds to this graphql field, and whether its a
-                            //one to many or many to one relation
-                            //
-                            for (col1, col2) in join_cols {
-                                new_col_offset += 1;
-                                let parent_pk = [&current_alias, ".", col1].concat();
-                                from.push_str(&parent_pk);
-                                from.push_str(" = ");
-                                from.push_str(&child_alias);
-                                from.push('.');
-                                from.push_str(col2);
-                                from.push_str(" AND ");
+                        }
+                        let condition = self
+                            .where_condition(sql, &child_field.node, child_node_index, &child_alias)
+                            .map_err(|e| e.in_field(child_key))?;
+                        conditions.extend(condition);
+                        let child_node = &self.g[child_node_index];
+                        match Page::new(&child_field.node).map_err(|e| e.in_field(child_key))? {
+                            //the page is selected for each parent separately
+                            Some(page) => {
+                                let order =
+                                    order_by_terms(child_node, &child_alias, &child_field.node)
+                                        .map_err(|e| e.in_field(child_key))?;
+                                sql.from.push_str(" LEFT JOIN LATERAL ");
+                                sql.from.push_str(&page.subquery(
+                                    child_node,
+                                    &child_alias,
+                                    &conditions,
+                                    &order,
+                                ));
+                                sql.from.push_str(" ON TRUE");
+                            }
+                            None => {
+                                sql.from.push_str(
+                                    &[" LEFT JOIN ", &child_node.sql_name(), " AS ", &child_alias]
+                                        .concat(),
+                                );
+                                sql.from.push_str(" ON ");
+                                sql.from.push_str(&conditions.join(" AND "));
                             }
-                            //remove trailing " and "
-                            from.drain(from.len() - 5..from.len());
-                            children.push((selection, child_node_index));
                         }
+                        children.push((
+                            child_field,
+                            child_node_index,
+                            max_id,
+                            graphql_fields.len() - 1,
+                        ));
                     }
                 }
             }
-            table_metadata.push(TableMetadata {
-                graphql_fields,
-                //the value at which primary keys start is the column offset before we started
-                //adding any new columns (column offset was copied before we started modifiying it
-                //this recursive call. The right hand is the column offset + the number of primary
-                //keys that this table has.)
-                primary_key_range: (column_offset
-                    ..column_offset + self.g[node_index].primary_keys.len()),
-            });
-
-            for child in children {
-                match self.build_selection(
+        }
+        let table_index = table_metadata.len();
+        table_metadata.push(TableMetadata {
+            graphql_fields,
+            //the value at which primary keys start is the column offset before we started
+            //adding any new columns (column offset was copied before we started modifiying it
+            //this recursive call. The right hand is the column offset + the number of primary
+            //keys that this table has.)
+            primary_key_range: (column_offset..column_offset + primary_keys.len()),
+        });
+
+        for (child_field, child_node_index, child_id, field_index) in children {
+            //the child's metadata is pushed next, so point the parent's field at it
+            let child_table_index = table_metadata.len();
+            table_metadata[table_index].graphql_fields[field_index]
+                .set_table_index(child_table_index);
+            let (col_offset, new_max_id) = self
+                .build_selection(
                     sql,
                     table_metadata,
-                    child.0,
-                    child.1,
+                    child_field,
+                    child_node_index,
                     new_col_offset,
-                    local_id,
-                ) {
-                    Ok((col_offset, new_local_id)) => {
-                        new_col_offset = col_offset;
-                        local_id = new_local_id
-                    }
-                    Err(e) => return Err(e),
-                }
-            }
+                    child_id,
+                    max_id,
+                )
+                .map_err(|e| e.in_field(response_key(&child_field.node)))?;
+            new_col_offset = col_offset;
+            max_id = new_max_id;
         }
-        Ok((new_col_offset, local_id))
+        Ok((new_col_offset, max_id))
     }
 
     //this method will try to identify whether it's an incoming or outgoing edge. If it's incoming
@@ -345,17 +661,17 @@ impl GraphQLSchema {
         &self,
         node_index: NodeIndex<u32>,
         field_name: &str,
+        response_key: &str,
         graphql_fields: &mut Vec<ColumnInfo>,
-    ) -> Result<(Zip<Iter<String>, Iter<String>>, NodeIndex<u32>), String> {
+    ) -> Result<(JoinColumns<'_>, NodeIndex<u32>), GraphQLError> {
         let mut incoming_edges = self
             .g
             .neighbors_directed(node_index, petgraph::EdgeDirection::Incoming)
             .detach();
 
-        while let Some(edge) = incoming_edges.next_edge(&self.g) {
+        while let Some((edge, node_index)) = incoming_edges.next(&self.g) {
             if self.g[edge].graphql_field_name.incoming == field_name {
-                graphql_fields.push(ColumnInfo::Foreign(field_name.to_string()));
-                let node_index = self.g.edge_endpoints(edge).unwrap().0;
+                graphql_fields.push(ColumnInfo::Foreign(response_key.to_string(), 0));
 
                 //if incoming child fields on left, not right
                 return Ok((
@@ -375,10 +691,9 @@ impl GraphQLSchema {
 
         //check if we're referring to some parent. Opposite to the incoming edges, read the right
         //most graphql_field_name tuple value (parent field name)
-        while let Some(edge) = outgoing_edges.next_edge(&self.g) {
+        while let Some((edge, node_index)) = outgoing_edges.next(&self.g) {
             if self.g[edge].graphql_field_name.outgoing == field_name {
-                graphql_fields.push(ColumnInfo::ForeignSingular(field_name.to_string()));
-                let node_index = self.g.edge_endpoints(edge).unwrap().1;
+                graphql_fields.push(ColumnInfo::ForeignSingular(response_key.to_string(), 0));
                 //if incoming child fields on right, not left
                 return Ok((
                     self.g[edge]
@@ -389,13 +704,21 @@ impl GraphQLSchema {
                 ));
             }
         }
-        let gql_type = self.g[node_index].table_name.to_case(Case::UpperCamel);
-        Err(format!(
-            "{} does not have selection {}",
-            gql_type, field_name
-        ))
+        Err(ErrorKind::UnknownField(self.g[node_index].type_name(), field_name.to_string()).into())
     }
-    fn table_alias(local_id: u8) -> String {
+    fn table_alias(local_id: usize) -> String {
         ["__table_", &local_id.to_string(), "__"].concat()
     }
 }
+
+fn unknown_operation(name: &str) -> GraphQLError {
+    ErrorKind::Operation(format!("Unknown operation named \"{}\".", name)).into()
+}
+
+//the key a field is written under in the response (its alias if it has one)
+pub fn response_key(field: &Field) -> &str {
+    match &field.alias {
+        Some(alias) => alias.node.as_str(),
+        None => field.name.node.as_str(),
+    }
+}
@@ -0,0 +1,139 @@
+use crate::build_schema::{GraphQLType, Operation};
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::fragments::select_type;
+use crate::generate_sql::{GraphQLSchema, JsonBuilderContext, SqlQueryComponents};
+use crate::schema_model::NODE_INTERFACE;
+use async_graphql_parser::types::Field;
+use async_graphql_parser::Positioned;
+use async_graphql_value::Value;
+use petgraph::prelude::NodeIndex;
+
+//the nodeId of the row of the table aliased table_alias, as a JSON array of the table's name
+//followed by the values of its primary keys (as text), which the JSON builder base64 encodes
+pub fn select_node_id(node: &GraphQLType, table_alias: &str) -> String {
+    let mut values = vec![["'", &node.table_name.replace('\'', "''"), "'"].concat()];
+    values.extend(
+        node.primary_keys
+            .iter()
+            .map(|pk| [table_alias, ".", pk, "::text"].concat()),
+    );
+    ["json_build_array(", &values.join(", "), ")::text"].concat()
+}
+
+impl GraphQLSchema {
+    //the object of the table that the nodeId argument of field refers to. Only the fragments of
+    //field on the type of that table (or on Node) are selected
+    pub fn build_node(
+        &self,
+        field: &Positioned<Field>,
+    ) -> Result<JsonBuilderContext, GraphQLError> {
+        let node_id = field
+            .node
+            .get_argument("nodeId")
+            .ok_or_else(|| ErrorKind::MissingArgument(String::from("nodeId")))?;
+        let (node_index, _) = self.decode_node_id(node_id)?;
+        let type_name = self.g[node_index].type_name();
+        let mut typed_field = field.clone();
+        typed_field.node.selection_set.node = select_type(
+            &field.node.selection_set.node,
+            &[&type_name, NODE_INTERFACE],
+        )?;
+        self.build_table_query(&typed_field, Operation::Query(false, node_index))
+    }
+
+    //the condition selecting the row (of the table aliased __table_0__) that field refers to, by
+    //either its nodeId argument or an argument for every primary key
+    pub fn primary_key_condition(
+        &self,
+        sql: &mut SqlQueryComponents,
+        field: &Field,
+        node_index: NodeIndex<u32>,
+    ) -> Result<String, GraphQLError> {
+        let node = &self.g[node_index];
+        if node.primary_keys.is_empty() {
+            return Err(ErrorKind::Internal(format!(
+                "{} has no primary key to select it by",
+                node.type_name()
+            ))
+            .into());
+        }
+        let node_id = field
+            .get_argument("nodeId")
+            .filter(|node_id| !matches!(node_id.node, Value::Null));
+        let mut conditions = vec![];
+        for (i, pk) in node.primary_keys.iter().enumerate() {
+            let pk_name = node.field_name(pk);
+            let pk_type = match node.field_to_types.get(&pk_name) {
+                Some((_, pk_type)) => pk_type,
+                None => return Err(ErrorKind::Internal(format!("No column {}", pk)).into()),
+            };
+            let pk_value = field.get_argument(&pk_name);
+            let placeholder = match (node_id, pk_value) {
+                (Some(node_id), None) => {
+                    let (id_node_index, values) = self.decode_node_id(node_id)?;
+                    if id_node_index != node_index {
+                        return Err(GraphQLError::new(
+                            ErrorKind::InvalidValue(format!(
+                                "Expected the nodeId of a {}, found that of a {}",
+                                node.type_name(),
+                                self.g[id_node_index].type_name()
+                            )),
+                            node_id.pos,
+                        ));
+                    }
+                    sql.push_text(Some(values[i].clone()), pk_type.inner())
+                }
+                (None, Some(pk_value)) => sql
+                    .push_param(&pk_value.node, pk_type)
+                    .map_err(|e| e.or_at(pk_value.pos))?,
+                (Some(node_id), Some(_)) => {
+                    return Err(GraphQLError::new(
+                        ErrorKind::InvalidValue(format!(
+                            "Expected either the nodeId or the primary keys of a {}, found both",
+                            node.type_name()
+                        )),
+                        node_id.pos,
+                    ))
+                }
+                (None, None) => return Err(ErrorKind::MissingArgument(pk.to_string()).into()),
+            };
+            conditions.push(["__table_0__.", pk, " = ", &placeholder].concat());
+        }
+        Ok(conditions.join(" AND "))
+    }
+
+    //the table and the values of the primary keys (as text) of the row that a nodeId refers to
+    fn decode_node_id(
+        &self,
+        value: &Positioned<Value>,
+    ) -> Result<(NodeIndex<u32>, Vec<String>), GraphQLError> {
+        let node_id = match &value.node {
+            Value::String(node_id) => node_id,
+            other => {
+                return Err(GraphQLError::new(
+                    ErrorKind::InvalidValue(format!("Expected ID!, found {}", other)),
+                    value.pos,
+                ))
+            }
+        };
+        let decoded = base64::decode(node_id)
+            .ok()
+            .and_then(|json| serde_json::from_slice::<Vec<String>>(&json).ok());
+        if let Some((table_name, values)) = decoded.as_ref().and_then(|values| values.split_first())
+        {
+            let node_index = self.g.node_indices().find(|node_index| {
+                let node = &self.g[*node_index];
+                node.table_name == *table_name
+                    && !node.primary_keys.is_empty()
+                    && node.primary_keys.len() == values.len()
+            });
+            if let Some(node_index) = node_index {
+                return Ok((node_index, values.to_vec()));
+            }
+        }
+        Err(GraphQLError::new(
+            ErrorKind::InvalidValue(format!("Invalid nodeId \"{}\"", node_id)),
+            value.pos,
+        ))
+    }
+}
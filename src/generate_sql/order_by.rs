@@ -0,0 +1,164 @@
+use crate::build_schema::{GraphQLType, PostgresType};
+use crate::error::{ErrorKind, GraphQLError};
+use async_graphql_parser::types::Field;
+use async_graphql_value::Value;
+use convert_case::{Case, Casing};
+use inflector::Inflector;
+
+//the suffixes of the orderBy values of a column, and whether they order it descending and with
+//nulls first (by default, nulls are larger than any other value)
+const DIRECTIONS: [(&str, bool, bool); 6] = [
+    ("ASC", false, false),
+    ("DESC", true, true),
+    ("ASC_NULLS_FIRST", false, true),
+    ("ASC_NULLS_LAST", false, false),
+    ("DESC_NULLS_FIRST", true, true),
+    ("DESC_NULLS_LAST", true, false),
+];
+
+//a column that a table is ordered by
+#[derive(Clone, Debug)]
+pub struct OrderTerm {
+    //the column, qualified by the alias of its table
+    pub column: String,
+    pub pg_type: PostgresType,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+impl OrderTerm {
+    //an ascending term for a primary key, which is never null
+    pub fn primary_key(table_alias: &str, node: &GraphQLType, pk: &str) -> OrderTerm {
+        let pg_type = node
+            .field_to_types
+            .values()
+            .find(|(col_name, _)| col_name == pk)
+            .map(|(_, pg_type)| pg_type.clone())
+            .unwrap_or(PostgresType::Str);
+        OrderTerm {
+            column: [table_alias, ".", pk].concat(),
+            pg_type,
+            descending: false,
+            nulls_first: false,
+        }
+    }
+
+    //the term ordering in the opposite direction
+    pub fn reversed(&self) -> OrderTerm {
+        OrderTerm {
+            descending: !self.descending,
+            nulls_first: !self.nulls_first,
+            ..self.clone()
+        }
+    }
+
+    //the term as an expression of ORDER BY
+    pub fn sql(&self) -> String {
+        [
+            self.column.as_str(),
+            if self.descending { " DESC" } else { " ASC" },
+            if self.nulls_first {
+                " NULLS FIRST"
+            } else {
+                " NULLS LAST"
+            },
+        ]
+        .concat()
+    }
+}
+
+//the name of the enum that lists of the table are ordered by, e.g SiteUsersOrderBy
+pub fn order_by_type_name(node: &GraphQLType) -> String {
+    [&node.type_name().to_plural(), "OrderBy"].concat()
+}
+
+//the values of the orderBy enum of a table (e.g DISPLAYNAME_DESC), along with the term they order
+//by (for a table aliased table_alias). Columns are sorted by name, and json and xml columns can't
+//be ordered
+pub fn order_by_values(node: &GraphQLType, table_alias: &str) -> Vec<(String, OrderTerm)> {
+    let mut columns = node
+        .field_to_types
+        .iter()
+        .filter(|(_, (_, pg_type))| {
+            !matches!(pg_type.inner(), PostgresType::Json | PostgresType::Xml)
+        })
+        .collect::<Vec<_>>();
+    columns.sort_by(|a, b| a.0.cmp(b.0));
+    let mut values = vec![];
+    for (field_name, (col_name, pg_type)) in columns {
+        for (suffix, descending, nulls_first) in DIRECTIONS {
+            values.push((
+                [&field_name.to_case(Case::UpperSnake), "_", suffix].concat(),
+                OrderTerm {
+                    column: [table_alias, ".", col_name].concat(),
+                    pg_type: pg_type.clone(),
+                    descending,
+                    nulls_first,
+                },
+            ));
+        }
+    }
+    values
+}
+
+//the terms that the orderBy argument of field orders the table aliased table_alias by. A single
+//value is coerced to a list of it, and columns given more than once are ordered by their first
+//value
+pub fn order_by_terms(
+    node: &GraphQLType,
+    table_alias: &str,
+    field: &Field,
+) -> Result<Vec<OrderTerm>, GraphQLError> {
+    let (value, pos) = match field.get_argument("orderBy") {
+        Some(order_by) => (&order_by.node, order_by.pos),
+        None => return Ok(vec![]),
+    };
+    order_values_terms(node, table_alias, value).map_err(|e| e.or_at(pos))
+}
+
+fn order_values_terms(
+    node: &GraphQLType,
+    table_alias: &str,
+    value: &Value,
+) -> Result<Vec<OrderTerm>, GraphQLError> {
+    let order_values = match value {
+        Value::Null => return Ok(vec![]),
+        Value::List(order_values) => order_values.iter().collect(),
+        other => vec![other],
+    };
+    let values = order_by_values(node, table_alias);
+    let mut terms = vec![];
+    for order_value in order_values {
+        let name = match order_value {
+            Value::Enum(name) => name.as_str(),
+            //variables give enum values as strings
+            Value::String(name) => name.as_str(),
+            other => {
+                return Err(ErrorKind::InvalidValue(format!(
+                    "Expected {}, found {}",
+                    order_by_type_name(node),
+                    other
+                ))
+                .into())
+            }
+        };
+        match values.iter().find(|(value_name, _)| value_name == name) {
+            //the rows are already ordered by a column given again (e.g [ID_DESC, ID_ASC]), so
+            //later terms of it have no effect
+            Some((_, term))
+                if terms
+                    .iter()
+                    .any(|other: &OrderTerm| other.column == term.column) => {}
+            Some((_, term)) => terms.push(term.clone()),
+            None => {
+                return Err(ErrorKind::InvalidValue(format!(
+                    "Value \"{}\" does not exist in \"{}\" enum.",
+                    name,
+                    order_by_type_name(node)
+                ))
+                .into())
+            }
+        }
+    }
+    Ok(terms)
+}
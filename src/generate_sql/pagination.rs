@@ -0,0 +1,83 @@
+use crate::build_schema::GraphQLType;
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::order_by::OrderTerm;
+use async_graphql_parser::types::Field;
+use async_graphql_value::Value;
+
+//the first and offset arguments of a list, which limit the objects of the list rather than the
+//rows of the joined query
+pub struct Page {
+    first: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl Page {
+    //the page of field, or None if it isn't paginated
+    pub fn new(field: &Field) -> Result<Option<Page>, GraphQLError> {
+        let page = Page {
+            first: page_argument(field, "first")?,
+            offset: page_argument(field, "offset")?,
+        };
+        if page.first.is_none() && page.offset.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(page))
+    }
+
+    //the rows of the table on this page, as a subquery aliased by table_alias. Every condition
+    //has to hold (they may refer to the parent of a LATERAL join), and rows are ordered by order
+    //and then the primary keys, so that pages are stable
+    pub fn subquery(
+        &self,
+        node: &GraphQLType,
+        table_alias: &str,
+        conditions: &[String],
+        order: &[OrderTerm],
+    ) -> String {
+        let mut subquery = ["(SELECT * FROM ", &node.sql_name(), " AS ", table_alias].concat();
+        if !conditions.is_empty() {
+            subquery.push_str(" WHERE ");
+            subquery.push_str(&conditions.join(" AND "));
+        }
+        let order = order
+            .iter()
+            .cloned()
+            .chain(
+                node.primary_keys
+                    .iter()
+                    .map(|pk| OrderTerm::primary_key(table_alias, node, pk)),
+            )
+            .map(|term| term.sql())
+            .collect::<Vec<String>>();
+        if !order.is_empty() {
+            subquery.push_str(" ORDER BY ");
+            subquery.push_str(&order.join(", "));
+        }
+        if let Some(first) = self.first {
+            subquery.push_str(&format!(" LIMIT {}", first));
+        }
+        if let Some(offset) = self.offset {
+            subquery.push_str(&format!(" OFFSET {}", offset));
+        }
+        subquery.push_str(") AS ");
+        subquery.push_str(table_alias);
+        subquery
+    }
+}
+
+pub fn page_argument(field: &Field, argument: &str) -> Result<Option<u64>, GraphQLError> {
+    match field.get_argument(argument) {
+        Some(value) => match &value.node {
+            Value::Null => Ok(None),
+            Value::Number(number) if number.is_u64() => Ok(number.as_u64()),
+            other => Err(GraphQLError::new(
+                ErrorKind::InvalidValue(format!(
+                    "Expected a non-negative Int for {}, found {}",
+                    argument, other
+                )),
+                value.pos,
+            )),
+        },
+        None => Ok(None),
+    }
+}
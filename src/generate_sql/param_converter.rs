@@ -0,0 +1,215 @@
+use crate::build_schema::{PostgresComposite, PostgresEnum, PostgresType};
+use crate::error::ErrorKind;
+use async_graphql_value::{indexmap::IndexMap, Name, Number, Value};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use std::convert::TryFrom;
+
+impl PostgresType {
+    //converts a GraphQL input value to the text representation that is bound as a query parameter
+    //(None binds NULL). The placeholder is cast from text to sql_type_name, so values are only
+    //ever interpreted as data, never as SQL
+    pub fn stringify_param(&self, value: &Value) -> Result<Option<String>, ErrorKind> {
+        if let Value::Null = value {
+            return if self.is_nullable() {
+                Ok(None)
+            } else {
+                Err(ErrorKind::InvalidValue(format!(
+                    "Expected {}, found null",
+                    self.graphql_type()
+                )))
+            };
+        }
+        let param = match (self.inner(), value) {
+            (PostgresType::Array(element), Value::List(items)) => array_literal(element, items)?,
+            //a single value is coerced to a list of one
+            (PostgresType::Array(element), value) => {
+                array_literal(element, std::slice::from_ref(value))?
+            }
+            (PostgresType::Int, Value::Number(n)) if is_i32(n) => n.to_string(),
+            (PostgresType::SmallInt, Value::Number(n)) if is_i16(n) => n.to_string(),
+            (PostgresType::BigInt, Value::Number(n)) if n.is_i64() => n.to_string(),
+            (PostgresType::Float | PostgresType::Real, Value::Number(n)) => n.to_string(),
+            //big numbers may also be given as strings, as that is how they are written
+            (PostgresType::BigInt, Value::String(s)) if s.parse::<i64>().is_ok() => s.to_string(),
+            (PostgresType::Numeric, Value::Number(n)) => n.to_string(),
+            (PostgresType::Numeric, Value::String(s)) if s.parse::<f64>().is_ok() => s.to_string(),
+            (PostgresType::Boolean, Value::Boolean(b)) => b.to_string(),
+            //dates and times are validated here, as Postgres accepts many more formats than the
+            //RFC 3339 ones that they are written in
+            (PostgresType::Timestamptz, Value::String(s))
+                if DateTime::parse_from_rfc3339(s).is_ok() =>
+            {
+                s.to_string()
+            }
+            //an offset is allowed but ignored, just like when Postgres casts text to a timestamp
+            (PostgresType::Timestamp, Value::String(s))
+                if NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+                    || DateTime::parse_from_rfc3339(s).is_ok() =>
+            {
+                s.to_string()
+            }
+            (PostgresType::Date, Value::String(s))
+                if NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() =>
+            {
+                s.to_string()
+            }
+            //like timestamps, an offset is ignored by time but kept by timetz (which uses the
+            //session's time zone if there is none)
+            (PostgresType::Time | PostgresType::Timetz, Value::String(s))
+                if NaiveTime::parse_from_str(s, "%H:%M:%S%.f").is_ok()
+                    || NaiveTime::parse_from_str(s, "%H:%M:%S%.f%#z").is_ok() =>
+            {
+                s.to_string()
+            }
+            (PostgresType::Composite(composite), Value::Object(fields)) => {
+                row_literal(composite, fields)?
+            }
+            (PostgresType::Enum(postgres_enum), Value::Enum(name)) => {
+                enum_label(postgres_enum, name)?
+            }
+            //enum values are given as strings in variables
+            (PostgresType::Enum(postgres_enum), Value::String(name)) => {
+                enum_label(postgres_enum, name)?
+            }
+            (PostgresType::Json, value) => {
+                match value.clone().into_const().map(|value| value.into_json()) {
+                    Some(Ok(json)) => json.to_string(),
+                    _ => {
+                        return Err(ErrorKind::InvalidValue(format!(
+                            "Expected JSON, found {}",
+                            value
+                        )))
+                    }
+                }
+            }
+            //the remaining types are given in their text representation
+            (
+                PostgresType::Str
+                | PostgresType::Char
+                | PostgresType::Citext
+                | PostgresType::Uuid
+                | PostgresType::Interval
+                | PostgresType::Bytea
+                | PostgresType::Inet
+                | PostgresType::Money
+                | PostgresType::Xml
+                | PostgresType::Other(_),
+                Value::String(s),
+            ) => s.to_string(),
+            (_, value) => {
+                return Err(ErrorKind::InvalidValue(format!(
+                    "Expected {}, found {}",
+                    self.graphql_type_name(),
+                    value
+                )))
+            }
+        };
+        Ok(Some(param))
+    }
+}
+
+impl PostgresType {
+    //whether text is a valid value of the type in the text representation that Postgres writes
+    //it in (e.g within cursors). Types whose text isn't checked here (such as intervals) are left
+    //for Postgres to reject
+    pub fn is_valid_text(&self, text: &str) -> bool {
+        let is_infinite = text == "infinity" || text == "-infinity";
+        match self.inner() {
+            PostgresType::Int => text.parse::<i32>().is_ok(),
+            PostgresType::SmallInt => text.parse::<i16>().is_ok(),
+            PostgresType::BigInt => text.parse::<i64>().is_ok(),
+            PostgresType::Float | PostgresType::Real | PostgresType::Numeric => {
+                text.parse::<f64>().is_ok()
+            }
+            PostgresType::Boolean => text == "true" || text == "false",
+            PostgresType::Uuid => {
+                text.len() == 36
+                    && text.char_indices().all(|(i, c)| match i {
+                        8 | 13 | 18 | 23 => c == '-',
+                        _ => c.is_ascii_hexdigit(),
+                    })
+            }
+            PostgresType::Date => {
+                is_infinite || NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok()
+            }
+            PostgresType::Timestamp => {
+                is_infinite || NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f").is_ok()
+            }
+            PostgresType::Timestamptz => {
+                is_infinite || DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z").is_ok()
+            }
+            PostgresType::Time => NaiveTime::parse_from_str(text, "%H:%M:%S%.f").is_ok(),
+            PostgresType::Timetz => NaiveTime::parse_from_str(text, "%H:%M:%S%.f%#z").is_ok(),
+            PostgresType::Enum(postgres_enum) => {
+                postgres_enum.labels.iter().any(|label| label == text)
+            }
+            _ => true,
+        }
+    }
+}
+
+fn is_i32(n: &Number) -> bool {
+    matches!(n.as_i64(), Some(n) if i32::try_from(n).is_ok())
+}
+
+//the text representation of a Postgres array, e.g {"a","b\"c",NULL}. Elements are always quoted
+//(escaping backslashes and quotes), so commas, braces and the text NULL within them are kept as data
+fn array_literal(element: &PostgresType, items: &[Value]) -> Result<String, ErrorKind> {
+    let mut literal = String::from("{");
+    for item in items {
+        match element.stringify_param(item)? {
+            Some(text) => literal.push_str(&quote(&text)),
+            None => literal.push_str("NULL"),
+        }
+        literal.push(',');
+    }
+    if literal.ends_with(',') {
+        literal.pop();
+    }
+    literal.push('}');
+    Ok(literal)
+}
+
+//the text representation of a composite value, e.g ("a",,"1"). Attributes that aren't given are
+//NULL, which is written as nothing at all
+fn row_literal(
+    composite: &PostgresComposite,
+    fields: &IndexMap<Name, Value>,
+) -> Result<String, ErrorKind> {
+    if let Some(unknown) = fields.keys().find(|name| {
+        !composite
+            .fields
+            .iter()
+            .any(|(field, _, _)| field == name.as_str())
+    }) {
+        return Err(ErrorKind::UnknownArgument(unknown.to_string()));
+    }
+    let mut attributes = vec![];
+    for (name, _, field_type) in &composite.fields {
+        let value = fields.get(name.as_str()).unwrap_or(&Value::Null);
+        attributes.push(match field_type.stringify_param(value)? {
+            Some(text) => quote(&text),
+            None => String::new(),
+        });
+    }
+    Ok(["(", &attributes.join(","), ")"].concat())
+}
+
+//quotes an element of an array or row literal
+fn quote(text: &str) -> String {
+    ["\"", &text.replace('\\', "\\\\").replace('"', "\\\""), "\""].concat()
+}
+
+fn enum_label(postgres_enum: &PostgresEnum, name: &str) -> Result<String, ErrorKind> {
+    match postgres_enum.label(name) {
+        Some(label) => Ok(label.to_string()),
+        None => Err(ErrorKind::InvalidValue(format!(
+            "Value \"{}\" does not exist in \"{}\" enum.",
+            name, postgres_enum.name
+        ))),
+    }
+}
+
+fn is_i16(n: &Number) -> bool {
+    matches!(n.as_i64(), Some(n) if i16::try_from(n).is_ok())
+}
@@ -1,9 +1,20 @@
-use crate::{build_schema::get_schema_and_client, generate_sql::JsonBuilderContext};
-
-
-fn get_graphql_schema() {
+use crate::{
+    build_schema::get_schema_and_client,
+    error::ErrorKind,
+    generate_sql::{JsonBuilderContext, RootField},
+};
+use async_graphql_parser::Pos;
+use serde_json::Map;
 
+impl RootField {
+    fn unwrap_sql(self) -> JsonBuilderContext {
+        match self {
+            RootField::Sql(ctx) => ctx,
+            RootField::Introspection(key, _) => panic!("{} was resolved without SQL", key),
+        }
+    }
 }
+
 #[actix_rt::test]
 async fn column_offsets() {
     let (pogg, _) = get_schema_and_client().await;
@@ -25,8 +36,15 @@ async fn column_offsets() {
         table_metadata,
         root_key_name: _,
         root_query_is_many: _,
-    } = pogg.parse_graphql(query).unwrap();
-    assert_eq!(table_metadata.get(0).unwrap().primary_key_range.start, 0);
+        params: _,
+        value_format: _,
+        connection: _,
+    } = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(table_metadata.first().unwrap().primary_key_range.start, 0);
     assert_eq!(table_metadata.get(1).unwrap().primary_key_range.start, 5);
 }
 
@@ -39,8 +57,10 @@ async fn test_invalid_root_query() {
               id
           }
         }";
-    let err = pogg.parse_graphql(query).expect_err("Wasn't Err");
-    assert_eq!(err.as_str(), "No operation named \"commentos\"");
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "No operation named \"commentos\"");
 }
 #[actix_rt::test]
 async fn test_invalid_syntax() {
@@ -51,9 +71,11 @@ async fn test_invalid_syntax() {
               id
             }
           ";
-    let err = pogg.parse_graphql(query).expect_err("Wasn't Err");
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
     assert_eq!(
-        err.as_str(),
+        err.to_string(),
         " --> 6:11\n  |\n6 |           \n  |           ^---\n  |\n  = expected selection"
     );
 }
@@ -68,9 +90,11 @@ async fn test_invalid_subchild() {
           }
         }";
 
-    let err = pogg.parse_graphql(query).expect_err("Wasn't Err");
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
     assert_eq!(
-        err.as_str(),
+        err.to_string(),
         "Post does not have selection nonExistentChild"
     );
 }
@@ -93,9 +117,11 @@ async fn test_error_propagation() {
             }
         }";
 
-    let err = pogg.parse_graphql(query).expect_err("Wasn't Err");
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
     assert_eq!(
-        err.as_str(),
+        err.to_string(),
         "Comment does not have selection nonExistentChild"
     );
 }
@@ -106,9 +132,11 @@ async fn test_no_root() {
     let query = "
         query{
         }";
-    let err = pogg.parse_graphql(query).expect_err("Wasn't Err");
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
     assert_eq!(
-        err.as_str(),
+        err.to_string(),
         " --> 3:9\n  |\n3 |         }\n  |         ^---\n  |\n  = expected selection"
     );
 }
@@ -123,8 +151,13 @@ async fn delete_mutation() {
           }
         }
         ";
-    let ctx = pogg.parse_graphql(gql_query).unwrap();
-    assert_eq!(ctx.sql_query, "WITH __table_0__ AS ( DELETE FROM site_user AS __table_0__ WHERE __table_0__.id = 1 RETURNING *) SELECT __table_0__.id AS __t0_pk0__, __table_0__.displayname AS __t0_c0__ FROM __table_0__");
+    let ctx = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(ctx.sql_query, "WITH __table_0__ AS ( DELETE FROM \"public\".\"site_user\" AS __table_0__ WHERE __table_0__.id = $1::text::int4 RETURNING *) SELECT __table_0__.id AS __t0_pk0__, __table_0__.displayname AS __t0_c0__ FROM __table_0__");
+    assert_eq!(ctx.params, vec![Some(String::from("1"))]);
 }
 
 #[actix_rt::test]
@@ -138,5 +171,1039 @@ async fn handle_named_operation() {
             }
         }
         ";
-    pogg.parse_graphql(gql_query).unwrap();
+    pogg.parse_graphql(gql_query, &Map::new(), None).unwrap();
+}
+
+#[actix_rt::test]
+async fn variables() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        mutation DELETE_SITE_USER($id: Int!) {
+          deleteSiteUser(id: $id){
+              displayname
+          }
+        }
+        ";
+    let variables = serde_json::json!({ "id": 1 });
+    let ctx = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(ctx.params, vec![Some(String::from("1"))]);
+}
+
+#[actix_rt::test]
+async fn default_and_nested_variables() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        mutation UPDATE_SITE_USER($id: Int = 1, $name: String!) {
+          updateSiteUser(id: $id, patch: { displayname: $name }){
+              id
+          }
+        }
+        ";
+    let variables = serde_json::json!({ "name": "new name" });
+    let ctx = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert!(ctx.sql_query.contains("displayname=$2::text::text"));
+    assert!(ctx.sql_query.contains("__table_0__.id = $1::text::int4"));
+    assert_eq!(
+        ctx.params,
+        vec![Some(String::from("1")), Some(String::from("new name"))]
+    );
+}
+
+#[actix_rt::test]
+async fn invalid_variables() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query SITE_USER($id: Int!) {
+          siteUser(id: $id){
+              id
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Variable \"$id\" of required type \"Int!\" was not provided."
+    );
+
+    let variables = serde_json::json!({ "id": "1; DROP TABLE site_user" });
+    let err = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Variable \"$id\" got invalid value \"1; DROP TABLE site_user\"; expected type \"Int!\"."
+    );
+
+    let gql_query = "
+        query {
+          siteUser(id: $undeclared){
+              id
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "Variable \"$undeclared\" is not defined.");
+}
+
+#[actix_rt::test]
+async fn many_joined_tables() {
+    let (pogg, _) = get_schema_and_client().await;
+    //more joins than table ids fit in a byte
+    let posts = (0..300)
+        .map(|i| format!("posts{}: postsByOwneruserid {{ id }}", i))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let gql_query = format!("query {{ siteUsers {{ id {} }} }}", posts);
+    let ctx = pogg
+        .parse_graphql(&gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert!(ctx.sql_query.contains("__table_300__"));
+}
+
+#[actix_rt::test]
+async fn mutations_share_their_sql_shape() {
+    let (pogg, _) = get_schema_and_client().await;
+    //the rows of nested lists are ordered by every mutation, so that their objects are grouped
+    for (gql_query, is_nested) in [
+        ("mutation { deleteSiteUser(id: 1) { id postsByOwneruserid { id } } }", true),
+        ("mutation { updateSiteUser(id: 1, patch: { views: 1 }) { id postsByOwneruserid { id } } }", true),
+        ("mutation { insertCompoundTable(parentId1: 5, parentId2: 5) { compoundChildTablesByParentId1AndParentId2 { id } } }", true),
+        ("mutation { insertMutationTest(name: \"a\") { id } }", false),
+        ("mutation { insertMutationTest { id } }", false),
+    ] {
+        let ctx = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .unwrap()
+            .remove(0)
+            .unwrap_sql();
+        let sql_query = &ctx.sql_query;
+        assert!(sql_query.contains(" RETURNING *) SELECT "), "{}", sql_query);
+        assert!(!sql_query.contains("  "), "{}", sql_query);
+        assert_eq!(sql_query.contains(" ORDER BY "), is_nested, "{}", sql_query);
+    }
+}
+
+#[actix_rt::test]
+async fn custom_scalar_variables() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        mutation UPDATE_TYPE_TEST($big: BigInt!, $precise: BigFloat, $docs: [JSON]) {
+          updateTypeTest(id: 1, patch: { big: $big, precise: $precise, docs: $docs }){
+              id
+          }
+        }
+        ";
+    let variables = serde_json::json!({
+        "big": 9007199254740993i64,
+        "precise": 1.5,
+        "docs": [[1, 2], 3, true]
+    });
+    let ctx = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert!(ctx.params.contains(&Some(String::from("9007199254740993"))));
+
+    let variables = serde_json::json!({ "big": "a lot" });
+    let err = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Variable \"$big\" got invalid value \"a lot\"; expected type \"BigInt!\"."
+    );
+}
+
+#[actix_rt::test]
+async fn invalid_variable_usages() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query SITE_USER($accountid: Int) {
+          siteUserByAccountid(accountid: $accountid){
+              id
+          }
+        }
+        ";
+    let variables = serde_json::json!({ "accountid": 1001 });
+    let err = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Variable \"$accountid\" of type \"Int\" used in position expecting type \"Int!\"."
+    );
+
+    let gql_query = "
+        query SITE_USERS($name: Int) {
+          siteUsers(where: { displayname: { eq: $name } }){
+              id
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Variable \"$name\" of type \"Int\" used in position expecting type \"String\"."
+    );
+
+    //a default makes a nullable variable usable where null isn't allowed
+    let gql_query = "
+        query SITE_USER($accountid: Int = 1001) {
+          siteUserByAccountid(accountid: $accountid){
+              id
+          }
+        }
+        ";
+    assert!(pogg.parse_graphql(gql_query, &Map::new(), None).is_ok());
+}
+
+#[actix_rt::test]
+async fn values_are_bound_as_params() {
+    let (pogg, client) = get_schema_and_client().await;
+    let gql_query = "
+        mutation {
+          insertMutationTest(name: \"Robert'); DROP TABLE mutation_test; --\", nullableFloat: null){
+              id
+              name
+              nullableFloat
+          }
+        }
+        ";
+    let ctx = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(
+        ctx.params,
+        vec![
+            Some(String::from("Robert'); DROP TABLE mutation_test; --")),
+            None
+        ]
+    );
+    let rows = client
+        .query(&ctx.sql_query, &ctx.sql_params())
+        .await
+        .unwrap();
+    let name: &str = rows.first().unwrap().get(2);
+    assert_eq!(name, "Robert'); DROP TABLE mutation_test; --");
+    let id: i32 = rows.first().unwrap().get(0);
+    client
+        .query("DELETE FROM mutation_test WHERE id = $1", &[&id])
+        .await
+        .unwrap();
+
+    let gql_query = "
+        query {
+          siteUser(id: \"1 OR 1 = 1\"){
+              id
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "Expected Int, found \"1 OR 1 = 1\"");
+}
+
+#[actix_rt::test]
+async fn multiple_root_fields() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          a: siteUsers {
+            id
+          }
+          posts {
+            id
+          }
+          b: siteUser(id: 1) {
+            id
+          }
+        }
+        ";
+    let contexts = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .into_iter()
+        .map(RootField::unwrap_sql)
+        .collect::<Vec<JsonBuilderContext>>();
+    assert_eq!(
+        contexts
+            .iter()
+            .map(|ctx| (ctx.root_key_name.as_str(), ctx.root_query_is_many))
+            .collect::<Vec<(&str, bool)>>(),
+        vec![("a", true), ("posts", true), ("b", false)]
+    );
+    assert_eq!(contexts[2].params, vec![Some(String::from("1"))]);
+}
+
+#[actix_rt::test]
+async fn fragment_cycles() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          siteUsers {
+            ...UserFields
+          }
+        }
+        fragment UserFields on SiteUser {
+          id
+          postsByOwneruserid {
+            ...PostFields
+          }
+        }
+        fragment PostFields on Post {
+          siteUserByOwneruserid {
+            ...UserFields
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Cannot spread fragment \"UserFields\" within itself via \"PostFields\"."
+    );
+
+    let gql_query = "
+        query {
+          siteUsers {
+            ...Missing
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "Unknown fragment \"Missing\".");
+}
+
+#[actix_rt::test]
+async fn fragments_are_merged() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          siteUsers {
+            id
+            ...UserFields
+            postsByOwneruserid {
+              id
+            }
+          }
+        }
+        fragment UserFields on SiteUser {
+          id
+          postsByOwneruserid {
+            ... on Post {
+              title
+            }
+          }
+        }
+        ";
+    let ctx = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    //fields selected both directly and through fragments are only selected (and joined) once
+    assert_eq!(ctx.table_metadata.len(), 2);
+    assert_eq!(ctx.table_metadata[0].graphql_fields.len(), 2);
+    assert_eq!(ctx.table_metadata[1].graphql_fields.len(), 2);
+}
+
+#[actix_rt::test]
+async fn conflicting_fields() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          siteUsers {
+            name: displayname
+            ...UserFields
+          }
+        }
+        fragment UserFields on SiteUser {
+          name: aboutme
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Fields \"name\" conflict because \"displayname\" and \"aboutme\" are different fields. \
+         Use different aliases on the fields to fetch both if this was intentional."
+    );
+
+    let gql_query = "
+        query {
+          siteUsers {
+            posts: postsByOwneruserid(first: 1) { id }
+            posts: postsByOwneruserid(first: 2) { id }
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Fields \"posts\" conflict because they have differing arguments. Use different aliases \
+         on the fields to fetch both if this was intentional."
+    );
+
+    //the same arguments in a different order don't conflict
+    let gql_query = "
+        query {
+          siteUsers {
+            posts: postsByOwneruserid(first: 1, offset: 1) { id }
+            posts: postsByOwneruserid(offset: 1, first: 1) { title }
+          }
+        }
+        ";
+    assert!(pogg.parse_graphql(gql_query, &Map::new(), None).is_ok());
+}
+
+#[actix_rt::test]
+async fn fragments_on_other_types() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          siteUsers {
+            ... on Post { id }
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Fragment cannot be spread here as objects of type \"SiteUser\" can never be of type \
+         \"Post\"."
+    );
+
+    let gql_query = "
+        query {
+          siteUsers {
+            id
+            ...PostFields
+          }
+        }
+        fragment PostFields on Post {
+          title
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Fragment \"PostFields\" cannot be spread here as objects of type \"SiteUser\" can never \
+         be of type \"Post\"."
+    );
+
+    //interfaces that the type implements can be spread
+    let gql_query = "
+        query {
+          siteUsers {
+            ... on Node { nodeId }
+            ... on SiteUser { id }
+          }
+        }
+        ";
+    assert!(pogg.parse_graphql(gql_query, &Map::new(), None).is_ok());
+}
+
+#[actix_rt::test]
+async fn skip_and_include() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query USERS($withPosts: Boolean!, $skipName: Boolean = true) {
+          siteUsers {
+            id
+            displayname @skip(if: $skipName)
+            reputation @include(if: false)
+            postsByOwneruserid @include(if: $withPosts) { id }
+            ... on SiteUser @skip(if: true) { views }
+            ...UserFields @include(if: true)
+          }
+        }
+        fragment UserFields on SiteUser {
+          upvotes @skip(if: false)
+          downvotes @include(if: $withPosts)
+        }
+        ";
+    let variables = serde_json::json!({ "withPosts": false });
+    let ctx = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    //only id and upvotes are left
+    assert_eq!(ctx.table_metadata.len(), 1);
+    assert_eq!(ctx.table_metadata[0].graphql_fields.len(), 2);
+
+    let variables = serde_json::json!({ "withPosts": null });
+    let err = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Variable \"$withPosts\" got invalid value null; expected type \"Boolean!\"."
+    );
+
+    let gql_query = "
+        query {
+          siteUsers {
+            id @deprecated
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "Unknown directive \"@deprecated\".");
+
+    let gql_query = "
+        query {
+          siteUsers {
+            id @skip(if: \"yes\")
+          }
+        }
+        ";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Directive \"@skip\" argument \"if\" expected a value of type \"Boolean!\", found \"yes\"."
+    );
+}
+
+#[actix_rt::test]
+async fn operation_name() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query USERS {
+          siteUsers{
+              id
+          }
+        }
+        query POSTS {
+          posts{
+              id
+          }
+        }
+        ";
+    let ctx = pogg
+        .parse_graphql(gql_query, &Map::new(), Some("POSTS"))
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(ctx.root_key_name, "posts");
+
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Must provide operation name if query contains multiple operations."
+    );
+
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), Some("COMMENTS"))
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "Unknown operation named \"COMMENTS\".");
+}
+
+#[actix_rt::test]
+async fn error_locations_and_paths() {
+    let (pogg, _) = get_schema_and_client().await;
+    let query = "
+query {
+  users: siteUsers {
+    posts: postsByOwneruserid {
+      nonExistentChild
+    }
+  }
+}";
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_json(),
+        serde_json::json!({
+            "message": "Post does not have selection nonExistentChild",
+            "locations": [{ "line": 5, "column": 7 }],
+            "path": ["users", "posts", "nonExistentChild"],
+            "extensions": { "code": "GRAPHQL_VALIDATION_FAILED" }
+        })
+    );
+
+    let query = "
+query {
+  siteUser(id: \"1\") {
+    id
+  }
+}";
+    let err = pogg
+        .parse_graphql(query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.kind,
+        ErrorKind::InvalidValue(String::from("Expected Int, found \"1\""))
+    );
+    assert_eq!(
+        err.pos,
+        Some(Pos {
+            line: 3,
+            column: 16
+        })
+    );
+    assert_eq!(err.path, vec!["siteUser"]);
+    assert_eq!(err.kind.code(), "BAD_USER_INPUT");
+}
+
+#[actix_rt::test]
+async fn invalid_selection_sets() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (query, message) in [
+        (
+            "query { siteUsers }",
+            "Field \"siteUsers\" of type \"SiteUser\" must have a selection of subfields.",
+        ),
+        (
+            "query { siteUsers { postsByOwneruserid } }",
+            "Field \"postsByOwneruserid\" of type \"Post\" must have a selection of subfields.",
+        ),
+        (
+            "query { siteUsers { id { id } } }",
+            "Field \"id\" must not have a selection since type \"Int!\" has no subfields.",
+        ),
+        (
+            "mutation { updateSiteUser(id: 1, patch: {}) { id } }",
+            "Expected patch to set at least one column",
+        ),
+        (
+            "mutation { updateSiteUser(id: 1, patch: 1) { id } }",
+            "Expected an object for patch, found 1",
+        ),
+        (
+            "mutation { insertMutationTest(unknown: 1) { id } }",
+            "Unknown argument \"unknown\" on field \"Mutation.insertMutationTest\".",
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(query, &Map::new(), None)
+            .expect_err(query);
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_enum_values() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          typeTests(where: { mood: { eq: ANGRY } }) {
+            id
+          }
+        }";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "Value \"ANGRY\" does not exist in \"Mood\" enum."
+    );
+
+    //variables give enum values as strings
+    let gql_query = "
+        query($mood: Mood) {
+          typeTests(where: { mood: { eq: $mood } }) {
+            id
+          }
+        }";
+    let variables = serde_json::json!({ "mood": "SO_SO" });
+    let ctx = pogg
+        .parse_graphql(gql_query, variables.as_object().unwrap(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(ctx.params, vec![Some(String::from("so so"))]);
+}
+
+#[actix_rt::test]
+async fn array_params() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        mutation {
+          updateTypeTest(id: 1, patch: { tags: [\"a,b\", \"c\\\\d\", null], moods: SO_SO }) {
+            id
+          }
+        }";
+    let ctx = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert_eq!(
+        ctx.params,
+        vec![
+            Some(String::from("1")),
+            Some(String::from("{\"a,b\",\"c\\\\d\",NULL}")),
+            //a single value is coerced to a list
+            Some(String::from("{\"so so\"}")),
+        ]
+    );
+    assert!(ctx.sql_query.contains("tags=$2::text::text[]"));
+
+    let gql_query = "
+        query {
+          typeTests(where: { scores: [1] }) {
+            id
+          }
+        }";
+    let err = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .expect_err("Wasn't Err");
+    assert_eq!(err.to_string(), "Expected a filter for scores, found [1]");
+}
+
+#[actix_rt::test]
+async fn composite_selections() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (selection, message, path) in [
+        (
+            "home",
+            "Field \"home\" of type \"Address\" must have a selection of subfields.",
+            vec!["typeTests", "home"],
+        ),
+        (
+            "spot { address { zip } }",
+            "Address does not have selection zip",
+            vec!["typeTests", "spot", "address", "zip"],
+        ),
+        (
+            "home { city { name } }",
+            "Field \"city\" must not have a selection since type \"String\" has no subfields.",
+            vec!["typeTests", "home", "city"],
+        ),
+    ] {
+        let gql_query = ["query { typeTests { ", selection, " } }"].concat();
+        let err = pogg
+            .parse_graphql(&gql_query, &Map::new(), None)
+            .expect_err("Wasn't Err");
+        assert_eq!(err.to_string(), message);
+        assert_eq!(err.path, path);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_timestamps() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (filter, message) in [
+        (
+            "happened: { eq: \"yesterday\" }",
+            "Expected Datetime, found \"yesterday\"",
+        ),
+        //Postgres would accept this, but it isn't RFC 3339
+        (
+            "happened: { gt: \"2021-01-01 10:00\" }",
+            "Expected Datetime, found \"2021-01-01 10:00\"",
+        ),
+        (
+            "day: { in: [\"01/02/2020\"] }",
+            "Expected Date, found \"01/02/2020\"",
+        ),
+    ] {
+        let gql_query = ["query { typeTests(where: { ", filter, " }) { id } }"].concat();
+        let err = pogg
+            .parse_graphql(&gql_query, &Map::new(), None)
+            .expect_err("Wasn't Err");
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn filter_sql() {
+    let (pogg, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          siteUsers(where: { reputation: { gt: 1 }, or: [{ id: { eq: 1 } }, { not: { views: { isNull: true } } }] }) {
+            id
+          }
+        }";
+    let ctx = pogg
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+        .unwrap_sql();
+    assert!(ctx.sql_query.ends_with(
+        " WHERE (__table_0__.reputation > $1::text::int4 AND (__table_0__.id = $2::text::int4 OR NOT (__table_0__.views IS NULL)))"
+    ), "{}", ctx.sql_query);
+
+    for (filter, message) in [
+        ("id: { like: \"1\" }", "Received unexpected argument like"),
+        ("id: { isNull: 1 }", "Expected Boolean, found 1"),
+        ("id: 1", "Expected a filter for id, found 1"),
+        ("not: 1", "Expected an object for where, found 1"),
+        ("unknown: { eq: 1 }", "Received unexpected argument unknown"),
+    ] {
+        let gql_query = ["query { siteUsers(where: { ", filter, " }) { id } }"].concat();
+        let err = pogg
+            .parse_graphql(&gql_query, &Map::new(), None)
+            .expect_err(filter);
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn relation_filter_errors() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (gql_query, message, path) in [
+        (
+            "query { posts { siteUserByOwneruserid(where: {}) { id } } }",
+            "Unknown argument \"where\" on field \"Post.siteUserByOwneruserid\".",
+            vec!["posts", "siteUserByOwneruserid"],
+        ),
+        (
+            "query { siteUsers(where: { postsByOwneruserid: { any: {} } }) { id } }",
+            "Received unexpected argument any",
+            vec!["siteUsers"],
+        ),
+        (
+            "query { siteUsers { postsByOwneruserid(where: { owneruserid: 1 }) { id } } }",
+            "Expected a filter for owneruserid, found 1",
+            vec!["siteUsers", "postsByOwneruserid"],
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .expect_err(gql_query);
+        assert_eq!(err.to_string(), message);
+        assert_eq!(err.path, path);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_order_by() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (gql_query, message) in [
+        (
+            "query { siteUsers(orderBy: [SCORE_ASC]) { id } }",
+            "Value \"SCORE_ASC\" does not exist in \"SiteUsersOrderBy\" enum.",
+        ),
+        (
+            "query { siteUsers(orderBy: 1) { id } }",
+            "Expected SiteUsersOrderBy, found 1",
+        ),
+        (
+            "query { siteUser(id: 1, orderBy: ID_ASC) { id } }",
+            "Unknown argument \"orderBy\" on field \"Query.siteUser\".",
+        ),
+        (
+            "query { posts { siteUserByOwneruserid(orderBy: ID_ASC) { id } } }",
+            "Unknown argument \"orderBy\" on field \"Post.siteUserByOwneruserid\".",
+        ),
+        //json columns can't be ordered
+        (
+            "query { siteUsers(orderBy: JSONFIELD_ASC) { id } }",
+            "Value \"JSONFIELD_ASC\" does not exist in \"SiteUsersOrderBy\" enum.",
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .expect_err(gql_query);
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_pages() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (gql_query, message) in [
+        (
+            "query { siteUsers(first: -1) { id } }",
+            "Expected a non-negative Int for first, found -1",
+        ),
+        (
+            "query { siteUsers { postsByOwneruserid(offset: \"1\") { id } } }",
+            "Expected a non-negative Int for offset, found \"1\"",
+        ),
+        (
+            "query { siteUser(id: 1, first: 1) { id } }",
+            "Unknown argument \"first\" on field \"Query.siteUser\".",
+        ),
+        (
+            "query { posts { siteUserByOwneruserid(offset: 1) { id } } }",
+            "Unknown argument \"offset\" on field \"Post.siteUserByOwneruserid\".",
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .expect_err(gql_query);
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn unknown_arguments() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (gql_query, message, path) in [
+        (
+            "query { siteUsers(bogus: 1) { id } }",
+            "Unknown argument \"bogus\" on field \"Query.siteUsers\".",
+            vec!["siteUsers"],
+        ),
+        (
+            "query { siteUser(id: 1, bogus: 2) { id } }",
+            "Unknown argument \"bogus\" on field \"Query.siteUser\".",
+            vec!["siteUser"],
+        ),
+        (
+            "query { siteUsers { posts: postsByOwneruserid(bogus: 3) { id } } }",
+            "Unknown argument \"bogus\" on field \"SiteUser.postsByOwneruserid\".",
+            vec!["siteUsers", "posts"],
+        ),
+        (
+            "query { siteUsers { id(x: 1) } }",
+            "Unknown argument \"x\" on field \"SiteUser.id\".",
+            vec!["siteUsers", "id"],
+        ),
+        (
+            "query { siteUsersConnection(offset: 5) { totalCount } }",
+            "Unknown argument \"offset\" on field \"Query.siteUsersConnection\".",
+            vec!["siteUsersConnection"],
+        ),
+        (
+            "query { siteUsersConnection { edges { node { id(x: 1) } } } }",
+            "Unknown argument \"x\" on field \"SiteUser.id\".",
+            vec!["siteUsersConnection", "edges", "node", "id"],
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .expect_err(gql_query);
+        assert_eq!(err.to_string(), message);
+        assert_eq!(err.path, path);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_connections() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (gql_query, message) in [
+        (
+            "query { siteUsersConnection(first: 1, last: 1) { totalCount } }",
+            "A connection can't be paginated by both first and last",
+        ),
+        (
+            "query { siteUsersConnection(after: \"abc\") { totalCount } }",
+            "Invalid cursor \"abc\"",
+        ),
+        (
+            "query { siteUsersConnection(before: 1) { totalCount } }",
+            "Expected Cursor, found 1",
+        ),
+        //the values of a cursor have to be valid for the columns they are compared with
+        (
+            "query { siteUsersConnection(after: \"WyJhYmMiXQ==\") { totalCount } }",
+            "Invalid cursor \"WyJhYmMiXQ==\"",
+        ),
+        (
+            "query { siteUsersConnection { edges { id } } }",
+            "SiteUsersEdge does not have selection id",
+        ),
+        (
+            "query { siteUsersConnection { pageInfo } }",
+            "Field \"pageInfo\" of type \"PageInfo\" must have a selection of subfields.",
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .expect_err(gql_query);
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_node_ids() {
+    let (pogg, _) = get_schema_and_client().await;
+    let user_id = base64::encode("[\"site_user\", \"1\"]");
+    let type_test_id = base64::encode("[\"type_test\", \"1\"]");
+    for (gql_query, message) in [
+        (
+            String::from("query { node(nodeId: \"abc\") { nodeId } }"),
+            String::from("Invalid nodeId \"abc\""),
+        ),
+        (
+            String::from("query { node(nodeId: 1) { nodeId } }"),
+            String::from("Expected ID!, found 1"),
+        ),
+        (
+            format!(
+                "query {{ siteUser(nodeId: \"{}\") {{ id }} }}",
+                type_test_id
+            ),
+            String::from("Expected the nodeId of a SiteUser, found that of a TypeTest"),
+        ),
+        (
+            format!(
+                "query {{ siteUser(id: 1, nodeId: \"{}\") {{ id }} }}",
+                user_id
+            ),
+            String::from(
+                "Expected either the nodeId or the primary keys of a SiteUser, found both",
+            ),
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(&gql_query, &Map::new(), None)
+            .expect_err(&gql_query);
+        assert_eq!(err.to_string(), message);
+    }
+}
+
+#[actix_rt::test]
+async fn invalid_unique_keys() {
+    let (pogg, _) = get_schema_and_client().await;
+    for (gql_query, message) in [
+        (
+            "query { siteUserByAccountid { id } }",
+            "Expected input field accountid",
+        ),
+        (
+            "query { siteUserByAccountid(accountid: null) { id } }",
+            "Expected Int!, found null",
+        ),
+        (
+            "query { compoundTableByParentId1AndName(parentId1: 1) { name } }",
+            "Expected input field name",
+        ),
+    ] {
+        let err = pogg
+            .parse_graphql(gql_query, &Map::new(), None)
+            .expect_err(gql_query);
+        assert_eq!(err.to_string(), message);
+    }
 }
@@ -0,0 +1,335 @@
+use super::GraphQLSchema;
+use crate::build_schema::PostgresType;
+use crate::error::{ErrorKind, GraphQLError};
+use crate::schema_model::{SchemaModel, TypeKind, TypeRef};
+use async_graphql_parser::{
+    types::{BaseType, Selection, SelectionSet, Type, VariableDefinition},
+    Positioned,
+};
+use async_graphql_value::{ConstValue, Name, Value};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+//coerces the request's variables against the operation's variable definitions (applying defaults
+//and checking types), returning the value of every declared variable
+pub fn resolve_variables(
+    schema: &GraphQLSchema,
+    variable_definitions: &[Positioned<VariableDefinition>],
+    variables: &Map<String, JsonValue>,
+) -> Result<HashMap<Name, ConstValue>, GraphQLError> {
+    let mut resolved = HashMap::new();
+    for definition in variable_definitions {
+        let VariableDefinition {
+            name,
+            var_type,
+            directives: _,
+            default_value,
+        } = &definition.node;
+        let value = match variables.get(name.node.as_str()) {
+            Some(json) => match ConstValue::from_json(json.clone()) {
+                Ok(value) => value,
+                Err(e) => {
+                    return Err(GraphQLError::new(
+                        ErrorKind::Variable(e.to_string()),
+                        definition.pos,
+                    ))
+                }
+            },
+            None => match default_value {
+                Some(default_value) => default_value.node.clone(),
+                None if var_type.node.nullable => ConstValue::Null,
+                None => {
+                    let message = format!(
+                        "Variable \"${}\" of required type \"{}\" was not provided.",
+                        name.node, var_type.node
+                    );
+                    return Err(GraphQLError::new(
+                        ErrorKind::Variable(message),
+                        definition.pos,
+                    ));
+                }
+            },
+        };
+        if !value_matches_type(schema, &value, &var_type.node) {
+            let message = format!(
+                "Variable \"${}\" got invalid value {}; expected type \"{}\".",
+                name.node, value, var_type.node
+            );
+            return Err(GraphQLError::new(
+                ErrorKind::Variable(message),
+                definition.pos,
+            ));
+        }
+        resolved.insert(name.node.clone(), value);
+    }
+    Ok(resolved)
+}
+
+fn value_matches_type(schema: &GraphQLSchema, value: &ConstValue, var_type: &Type) -> bool {
+    if let ConstValue::Null = value {
+        return var_type.nullable;
+    }
+    match &var_type.base {
+        //input coercion allows a single value to be passed where a list is expected
+        BaseType::List(item_type) => match value {
+            ConstValue::List(items) => items
+                .iter()
+                .all(|item| value_matches_type(schema, item, item_type)),
+            other => value_matches_type(schema, other, item_type),
+        },
+        BaseType::Named(type_name) => match type_name.as_str() {
+            "Int" => match value {
+                ConstValue::Number(n) => {
+                    matches!(n.as_i64(), Some(n) if i32::try_from(n).is_ok())
+                }
+                _ => false,
+            },
+            "Float" => matches!(value, ConstValue::Number(_)),
+            "String" => matches!(value, ConstValue::String(_)),
+            "Boolean" => matches!(value, ConstValue::Boolean(_)),
+            "ID" => match value {
+                ConstValue::String(_) => true,
+                ConstValue::Number(n) => n.is_i64(),
+                _ => false,
+            },
+            type_name => {
+                let column_types = schema.column_types_named(type_name);
+                if column_types.is_empty() {
+                    //input objects are checked when their fields are converted to columns, and the
+                    //values of orderBy enums when the order is built
+                    matches!(value, ConstValue::Object(_) | ConstValue::String(_))
+                } else {
+                    //custom scalars and enums accept the same values as literals do. Several
+                    //column types can share a scalar (e.g Datetime), so any of them may accept it
+                    let value = value.clone().into_value();
+                    column_types
+                        .iter()
+                        .any(|pg_type| pg_type.stringify_param(&value).is_ok())
+                }
+            }
+        },
+    }
+}
+
+impl GraphQLSchema {
+    //the column types (without nullability or arrays) that are given as values of the GraphQL
+    //type named type_name, e.g the int8 columns of BigInt, or the composite of AddressInput
+    fn column_types_named(&self, type_name: &str) -> Vec<&PostgresType> {
+        let mut column_types = vec![];
+        for node in self.g.node_weights() {
+            for (_, pg_type) in node.field_to_types.values() {
+                collect_types_named(pg_type, type_name, &mut column_types);
+            }
+        }
+        column_types
+    }
+}
+
+fn collect_types_named<'a>(
+    pg_type: &'a PostgresType,
+    type_name: &str,
+    column_types: &mut Vec<&'a PostgresType>,
+) {
+    let named_type = pg_type.named_type();
+    let input_name = match named_type {
+        PostgresType::Composite(composite) => {
+            for (_, _, field_type) in &composite.fields {
+                collect_types_named(field_type, type_name, column_types);
+            }
+            [&composite.name, "Input"].concat()
+        }
+        other => other.graphql_type_name().to_string(),
+    };
+    if input_name == type_name {
+        column_types.push(named_type);
+    }
+}
+
+//checks that every variable used in selection_set (whose fields belong to parent_type) is used
+//where a value of its declared type is allowed, e.g that an Int variable isn't given to an Int!
+//argument. Fields and arguments that don't exist are left for the SQL generation to reject
+pub fn check_variable_usages(
+    model: &SchemaModel,
+    variable_definitions: &[Positioned<VariableDefinition>],
+    selection_set: &SelectionSet,
+    parent_type: &str,
+) -> Result<(), GraphQLError> {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                let field_definition = model.field(parent_type, &field.node.name.node);
+                if let Some(field_definition) = field_definition {
+                    for (name, value) in &field.node.arguments {
+                        if let Some(argument) = field_definition
+                            .arguments
+                            .iter()
+                            .find(|argument| argument.name == name.node)
+                        {
+                            check_value_usages(
+                                model,
+                                variable_definitions,
+                                value,
+                                &argument.value_type,
+                            )?;
+                        }
+                    }
+                    check_variable_usages(
+                        model,
+                        variable_definitions,
+                        &field.node.selection_set.node,
+                        field_definition.field_type.named_type(),
+                    )?;
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                let type_name = match &fragment.node.type_condition {
+                    Some(condition) => condition.node.on.node.as_str(),
+                    None => parent_type,
+                };
+                check_variable_usages(
+                    model,
+                    variable_definitions,
+                    &fragment.node.selection_set.node,
+                    type_name,
+                )?;
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_value_usages(
+    model: &SchemaModel,
+    variable_definitions: &[Positioned<VariableDefinition>],
+    value: &Positioned<Value>,
+    location: &TypeRef,
+) -> Result<(), GraphQLError> {
+    match &value.node {
+        Value::Variable(name) => {
+            let definition = variable_definitions
+                .iter()
+                .find(|definition| definition.node.name.node == *name);
+            if let Some(definition) = definition {
+                let mut variable_type = type_ref(&definition.node.var_type.node);
+                //a nullable variable with a default can be used where null isn't allowed
+                if let (TypeRef::NonNull(_), Some(default_value)) =
+                    (location, &definition.node.default_value)
+                {
+                    if default_value.node != ConstValue::Null
+                        && !matches!(variable_type, TypeRef::NonNull(_))
+                    {
+                        variable_type = variable_type.non_null();
+                    }
+                }
+                if !is_compatible(&variable_type, location) {
+                    let message = format!(
+                        "Variable \"${}\" of type \"{}\" used in position expecting type \"{}\".",
+                        name, definition.node.var_type.node, location
+                    );
+                    return Err(GraphQLError::new(ErrorKind::Variable(message), value.pos));
+                }
+            }
+        }
+        Value::List(items) => {
+            if let TypeRef::List(item_type) = location.clone().nullable() {
+                for item in items {
+                    let item = Positioned::new(item.clone(), value.pos);
+                    check_value_usages(model, variable_definitions, &item, &item_type)?;
+                }
+            }
+        }
+        Value::Object(fields) => {
+            let input_fields = model.type_definition(location.named_type()).and_then(
+                |definition| match &definition.kind {
+                    TypeKind::InputObject(input_fields) => Some(input_fields),
+                    _ => None,
+                },
+            );
+            if let Some(input_fields) = input_fields {
+                for (name, field_value) in fields {
+                    if let Some(input_field) = input_fields
+                        .iter()
+                        .find(|input_field| input_field.name == name.as_str())
+                    {
+                        let field_value = Positioned::new(field_value.clone(), value.pos);
+                        check_value_usages(
+                            model,
+                            variable_definitions,
+                            &field_value,
+                            &input_field.value_type,
+                        )?;
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn type_ref(var_type: &Type) -> TypeRef {
+    let base = match &var_type.base {
+        BaseType::Named(name) => TypeRef::named(name),
+        BaseType::List(item_type) => type_ref(item_type).list(),
+    };
+    if var_type.nullable {
+        base
+    } else {
+        base.non_null()
+    }
+}
+
+//whether a variable of variable_type can be used where a value of location is expected
+fn is_compatible(variable_type: &TypeRef, location: &TypeRef) -> bool {
+    match (variable_type, location) {
+        (TypeRef::NonNull(variable_type), TypeRef::NonNull(location)) => {
+            is_compatible(variable_type, location)
+        }
+        (_, TypeRef::NonNull(_)) => false,
+        (TypeRef::NonNull(variable_type), location) => is_compatible(variable_type, location),
+        (TypeRef::List(variable_type), TypeRef::List(location)) => {
+            is_compatible(variable_type, location)
+        }
+        (TypeRef::Named(variable_type), TypeRef::Named(location)) => variable_type == location,
+        _ => false,
+    }
+}
+
+//replaces every variable used in an argument of selection_set (at any depth) by its value
+pub fn substitute_variables(
+    selection_set: &mut SelectionSet,
+    variables: &HashMap<Name, ConstValue>,
+) -> Result<(), GraphQLError> {
+    for selection in &mut selection_set.items {
+        match &mut selection.node {
+            Selection::Field(field) => {
+                for (_, value) in &mut field.node.arguments {
+                    let pos = value.pos;
+                    let resolved =
+                        value
+                            .node
+                            .clone()
+                            .into_const_with(|name| match variables.get(&name) {
+                                Some(value) => Ok(value.clone()),
+                                None => Err(GraphQLError::new(
+                                    ErrorKind::Variable(format!(
+                                        "Variable \"${}\" is not defined.",
+                                        name
+                                    )),
+                                    pos,
+                                )),
+                            })?;
+                    value.node = resolved.into_value();
+                }
+                substitute_variables(&mut field.node.selection_set.node, variables)?;
+            }
+            Selection::InlineFragment(fragment) => {
+                substitute_variables(&mut fragment.node.selection_set.node, variables)?
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,446 @@
+use super::{
+    EnumValue, FieldDefinition, InputValue, SchemaModel, TypeDefinition, TypeKind, TypeRef,
+    BUILTIN_SCALARS,
+};
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::response_key;
+use async_graphql_parser::types::{Field, Selection};
+use async_graphql_value::Value;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+//answers the __schema and __type meta fields. Every type of the model (along with the built in
+//scalars and the introspection types themselves) is resolved lazily against the selection, as the
+//type references of fields and arguments can be followed indefinitely
+#[derive(Clone, Default)]
+pub struct Introspection {
+    types: Vec<TypeDefinition>,
+    type_indices: HashMap<String, usize>,
+}
+
+//the directives that queries can use, with the descriptions of the directive and its if argument
+const DIRECTIVES: [(&str, &str, &str); 2] = [
+    (
+        "include",
+        "Directs the executor to include this field or fragment only when the `if` argument is \
+         true.",
+        "Included when true.",
+    ),
+    (
+        "skip",
+        "Directs the executor to skip this field or fragment when the `if` argument is true.",
+        "Skipped when true.",
+    ),
+];
+
+//a type as seen by __Type, which is either a named type or a wrapper around another type
+enum TypeView<'a> {
+    Definition(&'a TypeDefinition),
+    List(&'a TypeRef),
+    NonNull(&'a TypeRef),
+}
+
+impl Introspection {
+    pub fn new(model: &SchemaModel) -> Introspection {
+        let mut types = model.types.clone();
+        for name in BUILTIN_SCALARS {
+            if !types
+                .iter()
+                .any(|type_definition| type_definition.name == name)
+            {
+                types.push(TypeDefinition::new(name, TypeKind::Scalar));
+            }
+        }
+        types.extend(meta_types());
+        let type_indices = types
+            .iter()
+            .enumerate()
+            .map(|(i, type_definition)| (type_definition.name.to_string(), i))
+            .collect();
+        Introspection {
+            types,
+            type_indices,
+        }
+    }
+
+    pub fn resolve_schema(&self, field: &Field) -> Result<JsonValue, GraphQLError> {
+        resolve_object("__Schema", field, |child| {
+            Ok(Some(match child.name.node.as_str() {
+                "description" | "subscriptionType" => JsonValue::Null,
+                "types" => resolve_list(self.types.iter(), |type_definition| {
+                    self.resolve_type(TypeView::Definition(type_definition), child)
+                })?,
+                "queryType" => self.resolve_named_type("Query", child)?,
+                "mutationType" => self.resolve_named_type("Mutation", child)?,
+                "directives" => resolve_list(DIRECTIVES.iter(), |directive| {
+                    self.resolve_directive(directive, child)
+                })?,
+                _ => return Ok(None),
+            }))
+        })
+    }
+
+    //__type(name: String!), which is null if there is no type with that name
+    pub fn resolve_type_by_name(&self, field: &Field) -> Result<JsonValue, GraphQLError> {
+        match field.get_argument("name").map(|name| &name.node) {
+            Some(Value::String(name)) => self.resolve_named_type(name, field),
+            Some(other) => {
+                Err(ErrorKind::InvalidValue(format!("Expected String!, found {}", other)).into())
+            }
+            None => Err(ErrorKind::MissingArgument(String::from("name")).into()),
+        }
+    }
+
+    fn resolve_named_type(&self, name: &str, field: &Field) -> Result<JsonValue, GraphQLError> {
+        match self.type_indices.get(name) {
+            Some(index) => self.resolve_type(TypeView::Definition(&self.types[*index]), field),
+            None => Ok(JsonValue::Null),
+        }
+    }
+
+    fn resolve_type_ref(
+        &self,
+        type_ref: &TypeRef,
+        field: &Field,
+    ) -> Result<JsonValue, GraphQLError> {
+        match type_ref {
+            TypeRef::Named(name) => self.resolve_named_type(name, field),
+            TypeRef::List(item_type) => self.resolve_type(TypeView::List(item_type), field),
+            TypeRef::NonNull(inner_type) => self.resolve_type(TypeView::NonNull(inner_type), field),
+        }
+    }
+
+    fn resolve_type(&self, view: TypeView, field: &Field) -> Result<JsonValue, GraphQLError> {
+        let definition = match view {
+            TypeView::Definition(definition) => Some(definition),
+            _ => None,
+        };
+        let kind = definition.map(|definition| &definition.kind);
+        resolve_object("__Type", field, |child| {
+            Ok(Some(match child.name.node.as_str() {
+                "kind" => JsonValue::from(match (&view, kind) {
+                    (TypeView::List(_), _) => "LIST",
+                    (TypeView::NonNull(_), _) => "NON_NULL",
+                    (_, Some(TypeKind::Object(_))) => "OBJECT",
+                    (_, Some(TypeKind::Interface(_))) => "INTERFACE",
+                    (_, Some(TypeKind::InputObject(_))) => "INPUT_OBJECT",
+                    (_, Some(TypeKind::Enum(_))) => "ENUM",
+                    (_, _) => "SCALAR",
+                }),
+                "name" => optional_string(definition.map(|definition| &definition.name)),
+                "description" => optional_string(
+                    definition.and_then(|definition| definition.description.as_ref()),
+                ),
+                "fields" => match kind {
+                    Some(TypeKind::Object(fields)) | Some(TypeKind::Interface(fields)) => {
+                        resolve_list(fields.iter(), |field| {
+                            self.resolve_field_definition(field, child)
+                        })?
+                    }
+                    _ => JsonValue::Null,
+                },
+                "interfaces" => match (definition, kind) {
+                    (Some(definition), Some(TypeKind::Object(_))) => {
+                        resolve_list(definition.interfaces.iter(), |name| {
+                            self.resolve_named_type(name, child)
+                        })?
+                    }
+                    _ => JsonValue::Null,
+                },
+                //the objects implementing an interface
+                "possibleTypes" => match (definition, kind) {
+                    (Some(definition), Some(TypeKind::Interface(_))) => resolve_list(
+                        self.types.iter().filter(|type_definition| {
+                            type_definition.interfaces.contains(&definition.name)
+                        }),
+                        |type_definition| {
+                            self.resolve_type(TypeView::Definition(type_definition), child)
+                        },
+                    )?,
+                    _ => JsonValue::Null,
+                },
+                "inputFields" => match kind {
+                    Some(TypeKind::InputObject(fields)) => resolve_list(fields.iter(), |field| {
+                        self.resolve_input_value(field, child)
+                    })?,
+                    _ => JsonValue::Null,
+                },
+                "enumValues" => match kind {
+                    Some(TypeKind::Enum(values)) => {
+                        resolve_list(values.iter(), |value| resolve_enum_value(value, child))?
+                    }
+                    _ => JsonValue::Null,
+                },
+                "ofType" => match &view {
+                    TypeView::List(inner_type) | TypeView::NonNull(inner_type) => {
+                        self.resolve_type_ref(inner_type, child)?
+                    }
+                    TypeView::Definition(_) => JsonValue::Null,
+                },
+                "specifiedByURL" | "specifiedByUrl" => JsonValue::Null,
+                _ => return Ok(None),
+            }))
+        })
+    }
+
+    fn resolve_field_definition(
+        &self,
+        definition: &FieldDefinition,
+        field: &Field,
+    ) -> Result<JsonValue, GraphQLError> {
+        resolve_object("__Field", field, |child| {
+            Ok(Some(match child.name.node.as_str() {
+                "name" => JsonValue::from(definition.name.as_str()),
+                "description" => optional_string(definition.description.as_ref()),
+                "args" => resolve_list(definition.arguments.iter(), |argument| {
+                    self.resolve_input_value(argument, child)
+                })?,
+                "type" => self.resolve_type_ref(&definition.field_type, child)?,
+                "isDeprecated" => JsonValue::Bool(false),
+                "deprecationReason" => JsonValue::Null,
+                _ => return Ok(None),
+            }))
+        })
+    }
+
+    fn resolve_input_value(
+        &self,
+        value: &InputValue,
+        field: &Field,
+    ) -> Result<JsonValue, GraphQLError> {
+        resolve_object("__InputValue", field, |child| {
+            Ok(Some(match child.name.node.as_str() {
+                "name" => JsonValue::from(value.name.as_str()),
+                "description" => optional_string(value.description.as_ref()),
+                "type" => self.resolve_type_ref(&value.value_type, child)?,
+                "isDeprecated" => JsonValue::Bool(false),
+                "defaultValue" | "deprecationReason" => JsonValue::Null,
+                _ => return Ok(None),
+            }))
+        })
+    }
+
+    fn resolve_directive(
+        &self,
+        (name, description, condition_description): &(&str, &str, &str),
+        field: &Field,
+    ) -> Result<JsonValue, GraphQLError> {
+        resolve_object("__Directive", field, |child| {
+            Ok(Some(match child.name.node.as_str() {
+                "name" => JsonValue::from(*name),
+                "description" => JsonValue::from(*description),
+                "locations" => JsonValue::from(vec!["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+                "args" => {
+                    let condition = InputValue {
+                        description: Some(condition_description.to_string()),
+                        ..InputValue::new("if", TypeRef::named("Boolean").non_null())
+                    };
+                    resolve_list([condition].iter(), |argument| {
+                        self.resolve_input_value(argument, child)
+                    })?
+                }
+                "isRepeatable" => JsonValue::Bool(false),
+                _ => return Ok(None),
+            }))
+        })
+    }
+}
+
+fn resolve_enum_value(value: &EnumValue, field: &Field) -> Result<JsonValue, GraphQLError> {
+    resolve_object("__EnumValue", field, |child| {
+        Ok(Some(match child.name.node.as_str() {
+            "name" => JsonValue::from(value.name.as_str()),
+            "description" => optional_string(value.description.as_ref()),
+            "isDeprecated" => JsonValue::Bool(false),
+            "deprecationReason" => JsonValue::Null,
+            _ => return Ok(None),
+        }))
+    })
+}
+
+//writes every selection of field using resolve_field, which returns None for fields that type_name
+//doesn't have. __typename is answered here for all introspection types
+fn resolve_object(
+    type_name: &str,
+    field: &Field,
+    resolve_field: impl Fn(&Field) -> Result<Option<JsonValue>, GraphQLError>,
+) -> Result<JsonValue, GraphQLError> {
+    let mut object = Map::new();
+    for selection in &field.selection_set.node.items {
+        if let Selection::Field(child) = &selection.node {
+            let key = response_key(&child.node);
+            let value = match child.node.name.node.as_str() {
+                "__typename" => JsonValue::from(type_name),
+                child_name => match resolve_field(&child.node) {
+                    Ok(Some(value)) => value,
+                    Ok(None) => {
+                        let kind =
+                            ErrorKind::UnknownField(type_name.to_string(), child_name.to_string());
+                        return Err(GraphQLError::new(kind, child.pos).in_field(key));
+                    }
+                    Err(e) => return Err(e.or_at(child.pos).in_field(key)),
+                },
+            };
+            object.insert(key.to_string(), value);
+        }
+    }
+    Ok(JsonValue::Object(object))
+}
+
+fn resolve_list<T>(
+    items: impl Iterator<Item = T>,
+    resolve_item: impl Fn(T) -> Result<JsonValue, GraphQLError>,
+) -> Result<JsonValue, GraphQLError> {
+    Ok(JsonValue::Array(
+        items
+            .map(resolve_item)
+            .collect::<Result<_, GraphQLError>>()?,
+    ))
+}
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    match value {
+        Some(value) => JsonValue::from(value.as_str()),
+        None => JsonValue::Null,
+    }
+}
+
+//the types of the introspection system itself, as defined by the spec
+fn meta_types() -> Vec<TypeDefinition> {
+    let named = TypeRef::named;
+    let non_null = |name: &str| TypeRef::named(name).non_null();
+    let list = |name: &str| TypeRef::named(name).non_null().list().non_null();
+    let with_include_deprecated = |field: FieldDefinition| FieldDefinition {
+        arguments: vec![InputValue::new("includeDeprecated", named("Boolean"))],
+        ..field
+    };
+    let object = |name: &str, fields: Vec<FieldDefinition>| {
+        TypeDefinition::new(name, TypeKind::Object(fields))
+    };
+    let enumeration = |name: &str, values: &[&str]| {
+        TypeDefinition::new(
+            name,
+            TypeKind::Enum(
+                values
+                    .iter()
+                    .map(|value| EnumValue {
+                        name: value.to_string(),
+                        description: None,
+                    })
+                    .collect(),
+            ),
+        )
+    };
+    vec![
+        object(
+            "__Schema",
+            vec![
+                FieldDefinition::new("description", named("String")),
+                FieldDefinition::new("types", list("__Type")),
+                FieldDefinition::new("queryType", non_null("__Type")),
+                FieldDefinition::new("mutationType", named("__Type")),
+                FieldDefinition::new("subscriptionType", named("__Type")),
+                FieldDefinition::new("directives", list("__Directive")),
+            ],
+        ),
+        object(
+            "__Type",
+            vec![
+                FieldDefinition::new("kind", non_null("__TypeKind")),
+                FieldDefinition::new("name", named("String")),
+                FieldDefinition::new("description", named("String")),
+                FieldDefinition::new("specifiedByURL", named("String")),
+                with_include_deprecated(FieldDefinition::new("fields", non_null("__Field").list())),
+                FieldDefinition::new("interfaces", non_null("__Type").list()),
+                FieldDefinition::new("possibleTypes", non_null("__Type").list()),
+                with_include_deprecated(FieldDefinition::new(
+                    "enumValues",
+                    non_null("__EnumValue").list(),
+                )),
+                with_include_deprecated(FieldDefinition::new(
+                    "inputFields",
+                    non_null("__InputValue").list(),
+                )),
+                FieldDefinition::new("ofType", named("__Type")),
+            ],
+        ),
+        object(
+            "__Field",
+            vec![
+                FieldDefinition::new("name", non_null("String")),
+                FieldDefinition::new("description", named("String")),
+                with_include_deprecated(FieldDefinition::new("args", list("__InputValue"))),
+                FieldDefinition::new("type", non_null("__Type")),
+                FieldDefinition::new("isDeprecated", non_null("Boolean")),
+                FieldDefinition::new("deprecationReason", named("String")),
+            ],
+        ),
+        object(
+            "__InputValue",
+            vec![
+                FieldDefinition::new("name", non_null("String")),
+                FieldDefinition::new("description", named("String")),
+                FieldDefinition::new("type", non_null("__Type")),
+                FieldDefinition::new("defaultValue", named("String")),
+                FieldDefinition::new("isDeprecated", non_null("Boolean")),
+                FieldDefinition::new("deprecationReason", named("String")),
+            ],
+        ),
+        object(
+            "__EnumValue",
+            vec![
+                FieldDefinition::new("name", non_null("String")),
+                FieldDefinition::new("description", named("String")),
+                FieldDefinition::new("isDeprecated", non_null("Boolean")),
+                FieldDefinition::new("deprecationReason", named("String")),
+            ],
+        ),
+        object(
+            "__Directive",
+            vec![
+                FieldDefinition::new("name", non_null("String")),
+                FieldDefinition::new("description", named("String")),
+                FieldDefinition::new("locations", list("__DirectiveLocation")),
+                with_include_deprecated(FieldDefinition::new("args", list("__InputValue"))),
+                FieldDefinition::new("isRepeatable", non_null("Boolean")),
+            ],
+        ),
+        enumeration(
+            "__TypeKind",
+            &[
+                "SCALAR",
+                "OBJECT",
+                "INTERFACE",
+                "UNION",
+                "ENUM",
+                "INPUT_OBJECT",
+                "LIST",
+                "NON_NULL",
+            ],
+        ),
+        enumeration(
+            "__DirectiveLocation",
+            &[
+                "QUERY",
+                "MUTATION",
+                "SUBSCRIPTION",
+                "FIELD",
+                "FRAGMENT_DEFINITION",
+                "FRAGMENT_SPREAD",
+                "INLINE_FRAGMENT",
+                "VARIABLE_DEFINITION",
+                "SCHEMA",
+                "SCALAR",
+                "OBJECT",
+                "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INTERFACE",
+                "UNION",
+                "ENUM",
+                "ENUM_VALUE",
+                "INPUT_OBJECT",
+                "INPUT_FIELD_DEFINITION",
+            ],
+        ),
+    ]
+}
@@ -0,0 +1,812 @@
+mod introspection;
+mod sdl;
+#[cfg(test)]
+#[path = "./test.rs"]
+mod test;
+use crate::build_schema::{GraphQLType, Operation, PostgresComposite, PostgresEnum, PostgresType};
+use crate::generate_sql::{
+    connection_type_name, edge_type_name, filter_operators, order_by_type_name, order_by_values,
+    GraphQLSchema, Operand,
+};
+use petgraph::prelude::NodeIndex;
+use petgraph::visit::EdgeRef;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use introspection::Introspection;
+
+//scalars that every GraphQL service has, so they are never declared in SDL
+pub const BUILTIN_SCALARS: [&str; 5] = ["Int", "Float", "String", "Boolean", "ID"];
+
+const DATE_TIME_SCALARS: [(&str, &str); 3] = [
+    (
+        "Datetime",
+        "A timestamp in RFC 3339 format. Timestamps with a time zone are written in UTC (e.g \
+         2021-01-01T10:00:00Z), and those without one have no offset (e.g 2021-01-01T10:00:00)",
+    ),
+    ("Date", "A calendar date in RFC 3339 format, e.g 2021-01-01"),
+    (
+        "Time",
+        "A time of day in RFC 3339 format. Times with a time zone have an offset (e.g \
+         10:00:00+02:00), and those without one don't (e.g 10:00:00)",
+    ),
+];
+
+//the interface of objects that can be fetched by their nodeId, which are those of tables with
+//primary keys
+pub const NODE_INTERFACE: &str = "Node";
+
+const CURSOR_SCALAR: (&str, &str) = (
+    "Cursor",
+    "An opaque position in a connection, which pages can start after or end before",
+);
+
+//the GraphQL types (and the operations that use them) exposed for the introspected database. This
+//is what the SDL is printed from, so that the printed schema always matches what parse_graphql
+//accepts
+#[derive(Clone, Default)]
+pub struct SchemaModel {
+    pub types: Vec<TypeDefinition>,
+}
+
+#[derive(Clone)]
+pub struct TypeDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: TypeKind,
+    //the names of the interfaces that an object implements
+    pub interfaces: Vec<String>,
+}
+
+#[derive(Clone)]
+pub enum TypeKind {
+    Scalar,
+    Object(Vec<FieldDefinition>),
+    Interface(Vec<FieldDefinition>),
+    InputObject(Vec<InputValue>),
+    Enum(Vec<EnumValue>),
+}
+
+#[derive(Clone)]
+pub struct FieldDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    pub arguments: Vec<InputValue>,
+    pub field_type: TypeRef,
+}
+
+#[derive(Clone)]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub value_type: TypeRef,
+}
+
+#[derive(Clone)]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl SchemaModel {
+    pub fn type_definition(&self, name: &str) -> Option<&TypeDefinition> {
+        self.types
+            .iter()
+            .find(|type_definition| type_definition.name == name)
+    }
+
+    //the field of an object or interface type
+    pub fn field(&self, type_name: &str, field_name: &str) -> Option<&FieldDefinition> {
+        match &self.type_definition(type_name)?.kind {
+            TypeKind::Object(fields) | TypeKind::Interface(fields) => fields
+                .iter()
+                .find(|field_definition| field_definition.name == field_name),
+            _ => None,
+        }
+    }
+
+    //the object types that a value of the type can be, which are the objects implementing it if
+    //it is an interface
+    pub fn possible_types(&self, type_name: &str) -> Vec<&str> {
+        match self.type_definition(type_name) {
+            Some(TypeDefinition {
+                kind: TypeKind::Interface(_),
+                ..
+            }) => self
+                .types
+                .iter()
+                .filter(|definition| definition.interfaces.iter().any(|name| name == type_name))
+                .map(|definition| definition.name.as_str())
+                .collect(),
+            Some(definition) => vec![definition.name.as_str()],
+            None => vec![],
+        }
+    }
+}
+
+impl TypeDefinition {
+    pub fn new(name: &str, kind: TypeKind) -> TypeDefinition {
+        TypeDefinition {
+            name: name.to_string(),
+            description: None,
+            kind,
+            interfaces: vec![],
+        }
+    }
+}
+
+impl FieldDefinition {
+    pub fn new(name: &str, field_type: TypeRef) -> FieldDefinition {
+        FieldDefinition {
+            name: name.to_string(),
+            description: None,
+            arguments: vec![],
+            field_type,
+        }
+    }
+}
+
+impl InputValue {
+    pub fn new(name: &str, value_type: TypeRef) -> InputValue {
+        InputValue {
+            name: name.to_string(),
+            description: None,
+            value_type,
+        }
+    }
+}
+
+impl TypeRef {
+    pub fn named(name: &str) -> TypeRef {
+        TypeRef::Named(name.to_string())
+    }
+    pub fn non_null(self) -> TypeRef {
+        TypeRef::NonNull(Box::new(self))
+    }
+    pub fn list(self) -> TypeRef {
+        TypeRef::List(Box::new(self))
+    }
+    //the name of the type without its list and non-null wrappers
+    pub fn named_type(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::List(inner) | TypeRef::NonNull(inner) => inner.named_type(),
+        }
+    }
+    //the type without its outer non-null wrapper
+    pub fn nullable(self) -> TypeRef {
+        match self {
+            TypeRef::NonNull(inner) => *inner,
+            other => other,
+        }
+    }
+    //wraps the type in NonNull unless nullable
+    fn with_nullability(self, nullable: bool) -> TypeRef {
+        if nullable {
+            self
+        } else {
+            self.non_null()
+        }
+    }
+}
+
+impl PostgresType {
+    //the GraphQL type of a column of this type, e.g [String]! for a NOT NULL text[] column
+    pub fn graphql_type(&self) -> TypeRef {
+        self.graphql_type_as(false)
+    }
+
+    //the type of arguments given for a column of this type, which only differs from graphql_type
+    //for composite types (as their objects can't be used as inputs)
+    pub fn graphql_input_type(&self) -> TypeRef {
+        self.graphql_type_as(true)
+    }
+
+    fn graphql_type_as(&self, input: bool) -> TypeRef {
+        match self {
+            PostgresType::Nullable(inner) => inner.graphql_type_as(input).nullable(),
+            PostgresType::Array(element) => {
+                TypeRef::List(Box::new(element.graphql_type_as(input))).non_null()
+            }
+            PostgresType::Composite(composite) if input => {
+                TypeRef::Named(input_type_name(composite)).non_null()
+            }
+            other => TypeRef::named(other.graphql_type_name()).non_null(),
+        }
+    }
+
+    //adds the enums, composites and custom scalars used by this type
+    fn collect_named_types<'a>(
+        &'a self,
+        enums: &mut BTreeMap<&'a str, &'a PostgresEnum>,
+        composites: &mut BTreeMap<&'a str, &'a PostgresComposite>,
+        scalars: &mut BTreeSet<&'a str>,
+    ) {
+        match self.named_type() {
+            PostgresType::Enum(postgres_enum) => {
+                enums.insert(&postgres_enum.name, postgres_enum);
+            }
+            PostgresType::Composite(composite) => {
+                composites.insert(&composite.name, composite);
+                for (_, _, field_type) in &composite.fields {
+                    field_type.collect_named_types(enums, composites, scalars);
+                }
+            }
+            pg_type if !BUILTIN_SCALARS.contains(&pg_type.graphql_type_name()) => {
+                scalars.insert(pg_type.graphql_type_name());
+            }
+            _ => {}
+        }
+    }
+}
+
+fn input_type_name(composite: &PostgresComposite) -> String {
+    [&composite.name, "Input"].concat()
+}
+
+//the filter of the columns of pg_type, e.g IntFilter, or StringListFilter for text[] columns
+fn column_filter_name(pg_type: &PostgresType) -> String {
+    match pg_type.inner() {
+        PostgresType::Array(element) => [element.graphql_type_name(), "ListFilter"].concat(),
+        other => [other.graphql_type_name(), "Filter"].concat(),
+    }
+}
+
+impl GraphQLSchema {
+    //the whole API as a GraphQL SDL document
+    pub fn print_schema(&self) -> String {
+        self.schema_model.to_string()
+    }
+
+    //the model of the tables and operations of the schema, which is built once by
+    //build_schema::create
+    pub fn build_schema_model(&self) -> SchemaModel {
+        let mut operations = self.field_to_operation.iter().collect::<Vec<_>>();
+        operations.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut query_fields = vec![];
+        let mut mutation_fields = vec![];
+        //input objects are only declared for the tables whose operations need them, except for
+        //filters, which every table has as its relations can be filtered
+        let mut patches = BTreeSet::new();
+        let mut connections = BTreeSet::new();
+        for (name, operation) in operations {
+            match operation {
+                Operation::Connection(node_index) => {
+                    connections.insert(*node_index);
+                    query_fields.push(FieldDefinition {
+                        arguments: self.connection_arguments(*node_index),
+                        ..FieldDefinition::new(
+                            name,
+                            TypeRef::Named(connection_type_name(&self.g[*node_index])).non_null(),
+                        )
+                    });
+                }
+                Operation::Node => query_fields.push(FieldDefinition {
+                    arguments: vec![InputValue::new("nodeId", TypeRef::named("ID").non_null())],
+                    ..FieldDefinition::new("node", TypeRef::named(NODE_INTERFACE))
+                }),
+                Operation::Query(true, node_index) => {
+                    query_fields.push(FieldDefinition {
+                        arguments: self.list_arguments(*node_index),
+                        ..FieldDefinition::new(
+                            name,
+                            self.object_type(*node_index).non_null().list().non_null(),
+                        )
+                    });
+                }
+                Operation::Query(false, node_index) => query_fields.push(FieldDefinition {
+                    arguments: self.primary_key_arguments(*node_index),
+                    ..FieldDefinition::new(name, self.object_type(*node_index))
+                }),
+                Operation::QueryByUnique(node_index, key) => query_fields.push(FieldDefinition {
+                    arguments: self.unique_key_arguments(*node_index, *key),
+                    ..FieldDefinition::new(name, self.object_type(*node_index))
+                }),
+                Operation::Delete(node_index) => mutation_fields.push(FieldDefinition {
+                    arguments: self.primary_key_arguments(*node_index),
+                    ..FieldDefinition::new(name, self.object_type(*node_index))
+                }),
+                Operation::DeleteByUnique(node_index, key) => {
+                    mutation_fields.push(FieldDefinition {
+                        arguments: self.unique_key_arguments(*node_index, *key),
+                        ..FieldDefinition::new(name, self.object_type(*node_index))
+                    })
+                }
+                Operation::Update(node_index) | Operation::UpdateByUnique(node_index, _) => {
+                    patches.insert(*node_index);
+                    let mut arguments = match operation {
+                        Operation::UpdateByUnique(_, key) => {
+                            self.unique_key_arguments(*node_index, *key)
+                        }
+                        _ => self.primary_key_arguments(*node_index),
+                    };
+                    arguments.push(InputValue::new(
+                        "patch",
+                        TypeRef::Named(self.patch_type_name(*node_index)).non_null(),
+                    ));
+                    mutation_fields.push(FieldDefinition {
+                        arguments,
+                        ..FieldDefinition::new(name, self.object_type(*node_index))
+                    });
+                }
+                Operation::Insert(node_index) => mutation_fields.push(FieldDefinition {
+                    arguments: self.insert_arguments(*node_index),
+                    ..FieldDefinition::new(name, self.object_type(*node_index).non_null())
+                }),
+            }
+        }
+
+        let mut types = vec![TypeDefinition::new("Query", TypeKind::Object(query_fields))];
+        if !mutation_fields.is_empty() {
+            types.push(TypeDefinition::new(
+                "Mutation",
+                TypeKind::Object(mutation_fields),
+            ));
+        }
+
+        let mut column_filters = BTreeMap::new();
+        let mut nodes = self.g.node_indices().collect::<Vec<NodeIndex<u32>>>();
+        nodes.sort_by_key(|node_index| self.g[*node_index].type_name());
+        if self
+            .g
+            .node_weights()
+            .any(|node| !node.primary_keys.is_empty())
+        {
+            types.push(TypeDefinition {
+                description: Some(String::from("An object with a globally unique nodeId")),
+                ..TypeDefinition::new(NODE_INTERFACE, TypeKind::Interface(vec![node_id_field()]))
+            });
+        }
+        for node_index in &nodes {
+            let interfaces = if self.g[*node_index].primary_keys.is_empty() {
+                vec![]
+            } else {
+                vec![NODE_INTERFACE.to_string()]
+            };
+            types.push(TypeDefinition {
+                description: self.g[*node_index].description.clone(),
+                interfaces,
+                ..TypeDefinition::new(
+                    &self.g[*node_index].type_name(),
+                    TypeKind::Object(self.object_fields(*node_index)),
+                )
+            });
+            if connections.contains(node_index) {
+                types.extend(self.connection_types(*node_index));
+            }
+        }
+        if !connections.is_empty() {
+            let cursor = TypeRef::named("Cursor");
+            let boolean = TypeRef::named("Boolean").non_null();
+            types.push(TypeDefinition {
+                description: Some(String::from("Where a page of a connection is")),
+                ..TypeDefinition::new(
+                    "PageInfo",
+                    TypeKind::Object(vec![
+                        FieldDefinition::new("hasNextPage", boolean.clone()),
+                        FieldDefinition::new("hasPreviousPage", boolean),
+                        FieldDefinition::new("startCursor", cursor.clone()),
+                        FieldDefinition::new("endCursor", cursor),
+                    ]),
+                )
+            });
+        }
+        for node_index in &nodes {
+            types.push(TypeDefinition::new(
+                &self.filter_type_name(*node_index),
+                TypeKind::InputObject(self.filter_fields(*node_index, &mut column_filters)),
+            ));
+            //the filter of relations to lists of this table
+            if self
+                .g
+                .edges_directed(*node_index, petgraph::EdgeDirection::Outgoing)
+                .next()
+                .is_some()
+            {
+                let filter_type = TypeRef::Named(self.filter_type_name(*node_index));
+                let quantifiers = [
+                    ("some", "Some of the related rows match"),
+                    ("every", "Every related row matches"),
+                    ("none", "None of the related rows match"),
+                ]
+                .iter()
+                .map(|(name, description)| InputValue {
+                    description: Some(description.to_string()),
+                    ..InputValue::new(name, filter_type.clone())
+                })
+                .collect();
+                types.push(TypeDefinition::new(
+                    &self.to_many_filter_type_name(*node_index),
+                    TypeKind::InputObject(quantifiers),
+                ));
+            }
+            let order_values = order_by_values(&self.g[*node_index], "")
+                .into_iter()
+                .map(|(name, _)| EnumValue {
+                    name,
+                    description: None,
+                })
+                .collect();
+            types.push(TypeDefinition::new(
+                &order_by_type_name(&self.g[*node_index]),
+                TypeKind::Enum(order_values),
+            ));
+            if patches.contains(node_index) {
+                types.push(TypeDefinition::new(
+                    &self.patch_type_name(*node_index),
+                    TypeKind::InputObject(column_values(&self.g[*node_index], true, |_| true)),
+                ));
+            }
+        }
+
+        for (name, pg_type) in column_filters {
+            let value_type = pg_type.graphql_input_type();
+            let operators = filter_operators(pg_type)
+                .into_iter()
+                .map(|(operator, operand)| {
+                    let operand_type = match operand {
+                        Operand::Value => value_type.clone().nullable(),
+                        Operand::List => value_type.clone().list(),
+                        Operand::Boolean => TypeRef::named("Boolean"),
+                        Operand::Pattern => TypeRef::named("String"),
+                    };
+                    InputValue::new(operator, operand_type)
+                })
+                .collect();
+            types.push(TypeDefinition {
+                description: Some(format!(
+                    "Filters {} columns. Every given operator has to match",
+                    pg_type.graphql_type().nullable()
+                )),
+                ..TypeDefinition::new(&name, TypeKind::InputObject(operators))
+            });
+        }
+
+        let mut enums = BTreeMap::new();
+        let mut composites = BTreeMap::new();
+        let mut scalars = BTreeSet::new();
+        for (_, pg_type) in self
+            .g
+            .node_weights()
+            .flat_map(|node| node.field_to_types.values())
+        {
+            pg_type.collect_named_types(&mut enums, &mut composites, &mut scalars);
+        }
+        for composite in composites.values() {
+            let fields = composite
+                .fields
+                .iter()
+                .map(|(name, _, field_type)| FieldDefinition::new(name, field_type.graphql_type()))
+                .collect();
+            types.push(TypeDefinition {
+                description: composite.description.clone(),
+                ..TypeDefinition::new(&composite.name, TypeKind::Object(fields))
+            });
+            let input_fields = composite
+                .fields
+                .iter()
+                .map(|(name, _, field_type)| InputValue::new(name, field_type.graphql_input_type()))
+                .collect();
+            types.push(TypeDefinition::new(
+                &input_type_name(composite),
+                TypeKind::InputObject(input_fields),
+            ));
+        }
+        for postgres_enum in enums.values() {
+            let values = postgres_enum
+                .labels
+                .iter()
+                .map(|label| EnumValue {
+                    name: PostgresEnum::value_name(label),
+                    description: None,
+                })
+                .collect();
+            types.push(TypeDefinition {
+                description: postgres_enum.description.clone(),
+                ..TypeDefinition::new(&postgres_enum.name, TypeKind::Enum(values))
+            });
+        }
+        //the date and time scalars are always declared, as clients commonly map them to their
+        //own date types
+        scalars.extend(DATE_TIME_SCALARS.iter().map(|(name, _)| *name));
+        if !connections.is_empty() {
+            scalars.insert(CURSOR_SCALAR.0);
+        }
+        for name in scalars {
+            types.push(TypeDefinition {
+                description: DATE_TIME_SCALARS
+                    .iter()
+                    .chain([CURSOR_SCALAR].iter())
+                    .find(|(scalar, _)| *scalar == name)
+                    .map(|(_, description)| description.to_string()),
+                ..TypeDefinition::new(name, TypeKind::Scalar)
+            });
+        }
+
+        SchemaModel { types }
+    }
+
+    //the columns of the table followed by its relations. Tables referring to this one are
+    //selected as lists, whilst the table this one refers to is a single (nullable if any of the
+    //referring columns are) object
+    fn object_fields(&self, node_index: NodeIndex<u32>) -> Vec<FieldDefinition> {
+        let node = &self.g[node_index];
+        let mut fields = vec![];
+        if !node.primary_keys.is_empty() {
+            fields.push(node_id_field());
+        }
+        fields.extend(column_values(node, false, |_| false).into_iter().map(
+            |InputValue {
+                 name,
+                 description,
+                 value_type,
+             }| FieldDefinition {
+                description,
+                ..FieldDefinition::new(&name, value_type)
+            },
+        ));
+
+        let mut relations = vec![];
+        for edge in self
+            .g
+            .edges_directed(node_index, petgraph::EdgeDirection::Incoming)
+        {
+            relations.push(FieldDefinition {
+                arguments: self.list_arguments(edge.source()),
+                ..FieldDefinition::new(
+                    &edge.weight().graphql_field_name.incoming,
+                    self.object_type(edge.source()).non_null().list().non_null(),
+                )
+            });
+        }
+        for edge in self
+            .g
+            .edges_directed(node_index, petgraph::EdgeDirection::Outgoing)
+        {
+            let nullable = edge.weight().incoming_node_cols.iter().any(|col| {
+                match node.field_to_types.get(&node.field_name(col)) {
+                    Some((_, pg_type)) => pg_type.is_nullable(),
+                    None => true,
+                }
+            });
+            relations.push(FieldDefinition::new(
+                &edge.weight().graphql_field_name.outgoing,
+                self.object_type(edge.target()).with_nullability(nullable),
+            ));
+        }
+        relations.sort_by(|a, b| a.name.cmp(&b.name));
+        fields.extend(relations);
+        fields
+    }
+
+    //the filters of the columns of the table (adding the filters of their types to
+    //column_filters), followed by those of its relations and the combinators
+    fn filter_fields<'a>(
+        &'a self,
+        node_index: NodeIndex<u32>,
+        column_filters: &mut BTreeMap<String, &'a PostgresType>,
+    ) -> Vec<InputValue> {
+        let node = &self.g[node_index];
+        let mut fields = column_values(node, true, |_| true);
+        for field in &mut fields {
+            let pg_type = &node.field_to_types[&field.name].1;
+            let column_filter = column_filter_name(pg_type);
+            field.value_type = TypeRef::Named(column_filter.clone());
+            column_filters.insert(column_filter, pg_type.inner());
+        }
+
+        let mut relations = vec![];
+        for edge in self
+            .g
+            .edges_directed(node_index, petgraph::EdgeDirection::Incoming)
+        {
+            relations.push(InputValue::new(
+                &edge.weight().graphql_field_name.incoming,
+                TypeRef::Named(self.to_many_filter_type_name(edge.source())),
+            ));
+        }
+        for edge in self
+            .g
+            .edges_directed(node_index, petgraph::EdgeDirection::Outgoing)
+        {
+            relations.push(InputValue::new(
+                &edge.weight().graphql_field_name.outgoing,
+                TypeRef::Named(self.filter_type_name(edge.target())),
+            ));
+        }
+        relations.sort_by(|a, b| a.name.cmp(&b.name));
+        fields.extend(relations);
+
+        let filter_name = self.filter_type_name(node_index);
+        fields.extend([
+            InputValue {
+                description: Some(String::from("Every filter in the list matches")),
+                ..InputValue::new("and", TypeRef::named(&filter_name).non_null().list())
+            },
+            InputValue {
+                description: Some(String::from("Any filter in the list matches")),
+                ..InputValue::new("or", TypeRef::named(&filter_name).non_null().list())
+            },
+            InputValue {
+                description: Some(String::from("The filter doesn't match")),
+                ..InputValue::new("not", TypeRef::named(&filter_name))
+            },
+        ]);
+        fields
+    }
+
+    //the arguments of lists of the table, which filter, order and paginate them
+    fn list_arguments(&self, node_index: NodeIndex<u32>) -> Vec<InputValue> {
+        vec![
+            InputValue::new("where", TypeRef::Named(self.filter_type_name(node_index))),
+            InputValue::new(
+                "orderBy",
+                TypeRef::Named(order_by_type_name(&self.g[node_index]))
+                    .non_null()
+                    .list(),
+            ),
+            InputValue {
+                description: Some(String::from("The number of objects to return")),
+                ..InputValue::new("first", TypeRef::named("Int"))
+            },
+            InputValue {
+                description: Some(String::from("The number of objects to skip")),
+                ..InputValue::new("offset", TypeRef::named("Int"))
+            },
+        ]
+    }
+
+    //the arguments of the connection of the table, which are those of its lists but paginated by
+    //cursors rather than offsets
+    fn connection_arguments(&self, node_index: NodeIndex<u32>) -> Vec<InputValue> {
+        let mut arguments = self.list_arguments(node_index);
+        arguments.retain(|argument| argument.name != "offset");
+        arguments.extend([
+            InputValue {
+                description: Some(String::from("The number of nodes from the end to return")),
+                ..InputValue::new("last", TypeRef::named("Int"))
+            },
+            InputValue {
+                description: Some(String::from("The cursor that the nodes come after")),
+                ..InputValue::new("after", TypeRef::named("Cursor"))
+            },
+            InputValue {
+                description: Some(String::from("The cursor that the nodes come before")),
+                ..InputValue::new("before", TypeRef::named("Cursor"))
+            },
+        ]);
+        arguments
+    }
+
+    //the connection type of the table, and the type of its edges
+    fn connection_types(&self, node_index: NodeIndex<u32>) -> [TypeDefinition; 2] {
+        let node = &self.g[node_index];
+        let edge_type = TypeRef::Named(edge_type_name(node));
+        let node_type = self.object_type(node_index).non_null();
+        [
+            TypeDefinition {
+                description: Some(format!("A page of {} objects", node.type_name())),
+                ..TypeDefinition::new(
+                    &connection_type_name(node),
+                    TypeKind::Object(vec![
+                        FieldDefinition::new("edges", edge_type.non_null().list().non_null()),
+                        FieldDefinition::new("nodes", node_type.clone().list().non_null()),
+                        FieldDefinition::new("pageInfo", TypeRef::named("PageInfo").non_null()),
+                        FieldDefinition {
+                            description: Some(String::from(
+                                "The number of objects matching the filter, across every page",
+                            )),
+                            ..FieldDefinition::new("totalCount", TypeRef::named("Int").non_null())
+                        },
+                    ]),
+                )
+            },
+            TypeDefinition::new(
+                &edge_type_name(node),
+                TypeKind::Object(vec![
+                    FieldDefinition::new("cursor", TypeRef::named("Cursor").non_null()),
+                    FieldDefinition::new("node", node_type),
+                ]),
+            ),
+        ]
+    }
+
+    //the row is selected either by every primary key or by its nodeId, so none of them are
+    //required on their own
+    fn primary_key_arguments(&self, node_index: NodeIndex<u32>) -> Vec<InputValue> {
+        let node = &self.g[node_index];
+        let mut arguments = node
+            .primary_keys
+            .iter()
+            .filter_map(|pk| {
+                let name = node.field_name(pk);
+                node.field_to_types.get(&name).map(|(_, pg_type)| {
+                    InputValue::new(&name, pg_type.graphql_input_type().nullable())
+                })
+            })
+            .collect::<Vec<InputValue>>();
+        arguments.push(InputValue::new("nodeId", TypeRef::named("ID")));
+        arguments
+    }
+
+    //the columns of a unique constraint, which are all required as the row is only selected by
+    //all of them
+    fn unique_key_arguments(&self, node_index: NodeIndex<u32>, key: usize) -> Vec<InputValue> {
+        let node = &self.g[node_index];
+        node.unique_constraints[key]
+            .iter()
+            .filter_map(|col| {
+                let name = node.field_name(col);
+                node.field_to_types.get(&name).map(|(_, pg_type)| {
+                    InputValue::new(&name, pg_type.graphql_input_type().nullable().non_null())
+                })
+            })
+            .collect()
+    }
+
+    //every column is an argument of the insert, which can only be omitted if the column is
+    //nullable or has a default
+    fn insert_arguments(&self, node_index: NodeIndex<u32>) -> Vec<InputValue> {
+        let node = &self.g[node_index];
+        column_values(node, true, |name| node.fields_with_defaults.contains(name))
+    }
+
+    fn object_type(&self, node_index: NodeIndex<u32>) -> TypeRef {
+        TypeRef::Named(self.g[node_index].type_name())
+    }
+    fn filter_type_name(&self, node_index: NodeIndex<u32>) -> String {
+        [&self.g[node_index].type_name(), "Filter"].concat()
+    }
+    fn to_many_filter_type_name(&self, node_index: NodeIndex<u32>) -> String {
+        [&self.g[node_index].type_name(), "ToManyFilter"].concat()
+    }
+    fn patch_type_name(&self, node_index: NodeIndex<u32>) -> String {
+        [&self.g[node_index].type_name(), "Patch"].concat()
+    }
+}
+
+//the columns of node sorted by name, typed by their output or input (if input is true) type and
+//described by their comment. A column is nullable if the column itself is or if force_nullable
+//returns true for it
+fn column_values(
+    node: &GraphQLType,
+    input: bool,
+    force_nullable: impl Fn(&str) -> bool,
+) -> Vec<InputValue> {
+    let mut columns = node
+        .field_to_types
+        .iter()
+        .map(|(name, (_, pg_type))| {
+            let value_type = pg_type.graphql_type_as(input);
+            InputValue {
+                description: node.field_descriptions.get(name).cloned(),
+                ..InputValue::new(
+                    name,
+                    if force_nullable(name) {
+                        value_type.nullable()
+                    } else {
+                        value_type
+                    },
+                )
+            }
+        })
+        .collect::<Vec<InputValue>>();
+    columns.sort_by(|a, b| a.name.cmp(&b.name));
+    columns
+}
+
+fn node_id_field() -> FieldDefinition {
+    FieldDefinition {
+        description: Some(String::from(
+            "A globally unique identifier of the object, which the node query fetches it by",
+        )),
+        ..FieldDefinition::new("nodeId", TypeRef::named("ID").non_null())
+    }
+}
@@ -0,0 +1,113 @@
+use super::{
+    EnumValue, FieldDefinition, InputValue, SchemaModel, TypeDefinition, TypeKind, TypeRef,
+    BUILTIN_SCALARS,
+};
+use std::fmt::{Display, Formatter, Result};
+
+impl Display for TypeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            TypeRef::Named(name) => write!(f, "{}", name),
+            TypeRef::List(item_type) => write!(f, "[{}]", item_type),
+            TypeRef::NonNull(inner_type) => write!(f, "{}!", inner_type),
+        }
+    }
+}
+
+impl Display for InputValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}: {}", self.name, self.value_type)
+    }
+}
+
+impl Display for FieldDefinition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.name)?;
+        if !self.arguments.is_empty() {
+            let arguments = self
+                .arguments
+                .iter()
+                .map(|argument| argument.to_string())
+                .collect::<Vec<String>>();
+            write!(f, "({})", arguments.join(", "))?;
+        }
+        write!(f, ": {}", self.field_type)
+    }
+}
+
+impl Display for EnumValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+//prints the SDL document, one definition per block in the order of types. The root types use the
+//default names, so no schema definition is needed
+impl Display for SchemaModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut first = true;
+        for TypeDefinition {
+            name,
+            description,
+            kind,
+            interfaces,
+        } in &self.types
+        {
+            if BUILTIN_SCALARS.contains(&name.as_str()) || name.starts_with("__") {
+                continue;
+            }
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write_description(f, description, "")?;
+            match kind {
+                TypeKind::Scalar => writeln!(f, "scalar {}", name)?,
+                TypeKind::Object(fields) | TypeKind::Interface(fields) => {
+                    match kind {
+                        TypeKind::Interface(_) => write!(f, "interface {}", name)?,
+                        _ => write!(f, "type {}", name)?,
+                    }
+                    if !interfaces.is_empty() {
+                        write!(f, " implements {}", interfaces.join(" & "))?;
+                    }
+                    writeln!(f, " {{")?;
+                    for field in fields {
+                        write_description(f, &field.description, "  ")?;
+                        writeln!(f, "  {}", field)?;
+                    }
+                    writeln!(f, "}}")?;
+                }
+                TypeKind::InputObject(fields) => {
+                    writeln!(f, "input {} {{", name)?;
+                    for field in fields {
+                        write_description(f, &field.description, "  ")?;
+                        writeln!(f, "  {}", field)?;
+                    }
+                    writeln!(f, "}}")?;
+                }
+                TypeKind::Enum(values) => {
+                    writeln!(f, "enum {} {{", name)?;
+                    for value in values {
+                        write_description(f, &value.description, "  ")?;
+                        writeln!(f, "  {}", value)?;
+                    }
+                    writeln!(f, "}}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+//descriptions are written as block strings on the lines above what they describe
+fn write_description(f: &mut Formatter<'_>, description: &Option<String>, indent: &str) -> Result {
+    if let Some(description) = description {
+        writeln!(f, "{}\"\"\"", indent)?;
+        for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+            writeln!(f, "{}{}", indent, line)?;
+        }
+        writeln!(f, "{}\"\"\"", indent)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use crate::build_schema::get_schema_and_client;
+use crate::generate_sql::RootField;
+use async_graphql_parser::parse_schema;
+use serde_json::{json, Map, Value};
+
+#[actix_rt::test]
+async fn sdl_is_valid() {
+    let (pogg, _) = get_schema_and_client().await;
+    let sdl = pogg.print_schema();
+    if let Err(e) = parse_schema(&sdl) {
+        panic!("{}\n\n{}", e, sdl);
+    }
+    //operations and columns are sorted, so the output doesn't depend on hash map order
+    assert_eq!(sdl, pogg.print_schema());
+}
+
+#[actix_rt::test]
+async fn sdl_types() {
+    let (pogg, _) = get_schema_and_client().await;
+    let sdl = pogg.print_schema();
+    for expected in [
+        "type Query {",
+        "  siteUser(id: Int, nodeId: ID): SiteUser\n",
+        "  node(nodeId: ID!): Node\n",
+        "  siteUserByAccountid(accountid: Int!): SiteUser\n",
+        "  compoundTableByParentId1AndName(parentId1: Int!, name: String!): CompoundTable\n",
+        "  deleteSiteUserByAccountid(accountid: Int!): SiteUser\n",
+        "  updateSiteUserByAccountid(accountid: Int!, patch: SiteUserPatch!): SiteUser\n",
+        "interface Node {\n",
+        "type SiteUser implements Node {\n",
+        "  nodeId: ID!\n",
+        "  siteUsers(where: SiteUserFilter, orderBy: [SiteUsersOrderBy!], first: Int, offset: Int): [SiteUser!]!\n",
+        "  siteUsersConnection(where: SiteUserFilter, orderBy: [SiteUsersOrderBy!], first: Int, last: Int, after: Cursor, before: Cursor): SiteUsersConnection!\n",
+        "type SiteUsersConnection {\n  edges: [SiteUsersEdge!]!\n  nodes: [SiteUser!]!\n  pageInfo: PageInfo!\n",
+        "type SiteUsersEdge {\n  cursor: Cursor!\n  node: SiteUser!\n}\n",
+        "  hasNextPage: Boolean!\n",
+        "scalar Cursor\n",
+        "enum SiteUsersOrderBy {\n  ABOUTME_ASC\n  ABOUTME_DESC\n  ABOUTME_ASC_NULLS_FIRST\n",
+        "  NULLABLE_FLOAT_DESC_NULLS_LAST\n",
+        "type Mutation {",
+        "  deleteSiteUser(id: Int, nodeId: ID): SiteUser\n",
+        "  updateSiteUser(id: Int, nodeId: ID, patch: SiteUserPatch!): SiteUser\n",
+        "  compoundTable(parentId1: Int, parentId2: Int, nodeId: ID): CompoundTable\n",
+        //serial columns have a default, so they can be omitted from inserts
+        "  insertMutationTest(id: Int, name: String!, nullableFloat: Float): MutationTest!\n",
+        "  lastaccessdate: Datetime\n",
+        "  jsonfield: JSON\n",
+        "  \"\"\"\n  The name shown next to the user's posts\n  \"\"\"\n  displayname: String!\n",
+        "  postsByOwneruserid(where: PostFilter, orderBy: [PostsOrderBy!], first: Int, offset: Int): [Post!]!\n",
+        "  postsByOwneruserid: PostToManyFilter\n",
+        "  siteUserByOwneruserid: SiteUserFilter\n",
+        "input PostToManyFilter {",
+        //owneruserid is nullable, whilst comment.postid isn't
+        "  siteUserByOwneruserid: SiteUser\n",
+        "  postByPostid: Post!\n",
+        "input SiteUserPatch {",
+        "scalar Datetime",
+        "\"\"\"\nA calendar date in RFC 3339 format, e.g 2021-01-01\n\"\"\"\nscalar Date\n",
+        "scalar Time",
+        "scalar JSON",
+        "scalar UUID",
+        "scalar BigFloat",
+        "\"\"\"\nHow a user is feeling\n\"\"\"\nenum Mood {\n  HAPPY\n  SAD\n  SO_SO\n}\n",
+        "  mood: Mood\n",
+        "  scores: [Int]!\n",
+        "  rating: Int!\n",
+        "  home: Address\n",
+        "  history: [Address]\n",
+        "  home: AddressInput\n",
+        "\"\"\"\nA postal address\n\"\"\"\ntype Address {\n  street: String\n  city: String\n  moods: [Mood]\n}\n",
+        "input AddressInput {\n  street: String\n  city: String\n  moods: [Mood]\n}\n",
+        "input LocationInput {\n  label: String\n  address: AddressInput\n}\n",
+        "  tags: [String]\n",
+        "  tags: StringListFilter\n",
+        "input MoodListFilter {\n  eq: [Mood]\n  ne: [Mood]\n  isNull: Boolean\n  contains: [Mood]\n  containedBy: [Mood]\n  overlaps: [Mood]\n}\n",
+        "  displayname: StringFilter\n",
+        "  and: [SiteUserFilter!]\n",
+        "  or: [SiteUserFilter!]\n",
+        "  not: SiteUserFilter\n",
+        "  in: [Int!]\n",
+        "  startsWith: String\n",
+        "input IntFilter {\n  eq: Int\n  ne: Int\n  lt: Int\n  lte: Int\n  gt: Int\n  gte: Int\n  in: [Int!]\n  notIn: [Int!]\n  isNull: Boolean\n}\n",
+        "input XMLFilter {\n  isNull: Boolean\n}\n",
+        "input AddressFilter {\n  eq: AddressInput\n  ne: AddressInput\n  isNull: Boolean\n}\n",
+        "  big: BigInt!\n",
+        "  search: String\n",
+    ] {
+        assert!(sdl.contains(expected), "Missing {:?} in\n{}", expected, sdl);
+    }
+    assert!(!sdl.contains("scalar Int\n"));
+}
+
+//resolves every root field of query, which are all expected to be introspection fields
+async fn introspect(query: &str) -> Map<String, Value> {
+    let (pogg, _) = get_schema_and_client().await;
+    pogg.parse_graphql(query, &Map::new(), None)
+        .unwrap()
+        .into_iter()
+        .map(|root_field| match root_field {
+            RootField::Introspection(key, value) => (key, value),
+            RootField::Sql(ctx) => panic!("{} was resolved with SQL", ctx.root_key_name),
+        })
+        .collect()
+}
+
+#[actix_rt::test]
+async fn introspect_schema() {
+    let data = introspect(
+        "
+        query IntrospectionQuery {
+          __schema {
+            queryType { name }
+            mutationType { name }
+            subscriptionType { name }
+            types { ...FullType }
+            directives { name locations args { name type { ...TypeRef } } }
+          }
+        }
+        fragment FullType on __Type {
+          kind
+          name
+          description
+          fields(includeDeprecated: true) {
+            name
+            args { name defaultValue type { ...TypeRef } }
+            type { ...TypeRef }
+            isDeprecated
+          }
+          inputFields { name type { ...TypeRef } }
+          interfaces { name }
+          enumValues(includeDeprecated: true) { name }
+          possibleTypes { name }
+        }
+        fragment TypeRef on __Type {
+          kind
+          name
+          ofType { kind name ofType { kind name ofType { kind name } } }
+        }",
+    )
+    .await;
+    let schema = &data["__schema"];
+    assert_eq!(schema["queryType"]["name"], "Query");
+    assert_eq!(schema["mutationType"]["name"], "Mutation");
+    assert!(schema["subscriptionType"].is_null());
+    let condition = json!([{
+        "name": "if",
+        "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "Boolean", "ofType": null } }
+    }]);
+    let locations = json!(["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]);
+    assert_eq!(
+        schema["directives"],
+        json!([
+            { "name": "include", "locations": locations, "args": condition },
+            { "name": "skip", "locations": locations, "args": condition },
+        ])
+    );
+
+    let types = schema["types"].as_array().unwrap();
+    let find_type = |name: &str| {
+        types
+            .iter()
+            .find(|t| t["name"] == name)
+            .unwrap_or_else(|| panic!("No type {}", name))
+    };
+    for (name, kind) in [
+        ("SiteUser", "OBJECT"),
+        ("SiteUserPatch", "INPUT_OBJECT"),
+        ("Datetime", "SCALAR"),
+        ("Int", "SCALAR"),
+        ("__Type", "OBJECT"),
+        ("__TypeKind", "ENUM"),
+    ] {
+        assert_eq!(find_type(name)["kind"], kind, "{}", name);
+    }
+
+    let site_user = find_type("SiteUser");
+    assert_eq!(site_user["description"], "A user of the site");
+    assert_eq!(site_user["interfaces"], json!([{ "name": "Node" }]));
+    let node = find_type("Node");
+    assert_eq!(node["kind"], "INTERFACE");
+    assert!(node["possibleTypes"]
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "name": "SiteUser" })));
+    let displayname = site_user["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|field| field["name"] == "displayname")
+        .unwrap();
+    assert_eq!(
+        displayname["type"],
+        json!({ "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "String", "ofType": null } })
+    );
+
+    let site_users = find_type("Query")["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|field| field["name"] == "siteUsers")
+        .unwrap();
+    assert_eq!(site_users["args"][0]["name"], "where");
+    assert_eq!(
+        site_users["type"]["ofType"]["ofType"]["ofType"],
+        json!({ "kind": "OBJECT", "name": "SiteUser" })
+    );
+}
+
+#[actix_rt::test]
+async fn introspect_type() {
+    let data = introspect(
+        "
+        query {
+          patch: __type(name: \"SiteUserPatch\") {
+            __typename
+            kind
+            inputFields { name }
+          }
+          siteUser: __type(name: \"SiteUser\") {
+            fields { name description }
+          }
+          mood: __type(name: \"Mood\") {
+            kind
+            enumValues { name }
+          }
+          missing: __type(name: \"Missing\") {
+            name
+          }
+          __typename
+        }",
+    )
+    .await;
+    assert_eq!(data["patch"]["__typename"], "__Type");
+    assert_eq!(data["patch"]["kind"], "INPUT_OBJECT");
+    assert!(data["patch"]["inputFields"]
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "name": "displayname" })));
+    assert!(data["siteUser"]["fields"].as_array().unwrap().contains(
+        &json!({ "name": "displayname", "description": "The name shown next to the user's posts" })
+    ));
+    assert_eq!(data["mood"]["kind"], "ENUM");
+    assert_eq!(
+        data["mood"]["enumValues"],
+        json!([{ "name": "HAPPY" }, { "name": "SAD" }, { "name": "SO_SO" }])
+    );
+    assert!(data["missing"].is_null());
+    assert_eq!(data["__typename"], "Query");
+}
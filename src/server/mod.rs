@@ -0,0 +1,81 @@
+use crate::error::{ErrorKind, GraphQLError};
+use crate::generate_sql::{GraphQLSchema, RootField};
+use crate::settings::Settings;
+use crate::state_machine_builder::JsonBuilder;
+use actix_web::{web, HttpResponse};
+use deadpool_postgres::Pool;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+#[cfg(test)]
+#[path = "./test.rs"]
+mod test;
+
+pub struct ServerData {
+    pub schema: GraphQLSchema,
+    pub pool: Pool,
+    pub settings: Settings,
+}
+
+//the standard GraphQL over HTTP POST body
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLRequest {
+    pub query: String,
+    #[serde(default)]
+    pub variables: Option<Map<String, Value>>,
+    #[serde(default)]
+    pub operation_name: Option<String>,
+}
+
+pub async fn graphql(
+    data: web::Data<ServerData>,
+    request: web::Json<GraphQLRequest>,
+) -> HttpResponse {
+    match execute(&data, &request).await {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body),
+        Err(e) => HttpResponse::Ok()
+            .content_type("application/json")
+            .body(json!({ "errors": [e.to_json()] }).to_string()),
+    }
+}
+
+async fn execute(data: &ServerData, request: &GraphQLRequest) -> Result<String, GraphQLError> {
+    let variables = request.variables.clone().unwrap_or_default();
+    let root_fields = data.schema.parse_graphql(
+        &request.query,
+        &variables,
+        request.operation_name.as_deref(),
+    )?;
+    let mut client = match data.pool.get().await {
+        Ok(client) => client,
+        Err(e) => return Err(ErrorKind::Database(e.to_string()).into()),
+    };
+    //root fields are executed one after the other, in the order they were requested. They share a
+    //transaction, so that the mutations of an operation are rolled back if any of them fails
+    let transaction = client.transaction().await?;
+    let mut builder = JsonBuilder::new();
+    for root_field in root_fields {
+        let ctx = match root_field {
+            RootField::Sql(ctx) => ctx,
+            RootField::Introspection(key, value) => {
+                builder.add_resolved_field(&key, &value);
+                continue;
+            }
+        };
+        if data.settings.features.log_sql {
+            println!("{}", ctx.sql_query);
+        }
+        let rows = transaction
+            .query(ctx.sql_query.as_str(), &ctx.sql_params())
+            .await
+            .map_err(|e| GraphQLError::from(e).in_field(&ctx.root_key_name))?;
+        builder
+            .add_root_field(&ctx, &rows)
+            .map_err(|e| e.in_field(&ctx.root_key_name))?;
+    }
+    transaction.commit().await?;
+    Ok(["{\"data\":", &builder.finish(), "}"].concat())
+}
@@ -0,0 +1,45 @@
+use super::*;
+use crate::build_schema;
+
+async fn get_server_data() -> ServerData {
+    let settings = Settings::new().unwrap();
+    let pool = settings.database.create_pool().unwrap();
+    let schema = build_schema::create(&pool.get().await.unwrap(), &settings).await;
+    ServerData {
+        schema,
+        pool,
+        settings,
+    }
+}
+
+#[actix_rt::test]
+async fn failed_mutations_are_rolled_back() {
+    let data = get_server_data().await;
+    //the insert succeeds, but the update violates the check of the positive_int domain
+    let request = GraphQLRequest {
+        query: String::from(
+            "
+            mutation {
+              insertMutationTest(name: \"rolled back\") { id }
+              updateTypeTest(id: 2, patch: { rating: -1 }) { id }
+            }",
+        ),
+        variables: None,
+        operation_name: None,
+    };
+    let err = execute(&data, &request).await.expect_err("Wasn't Err");
+    assert_eq!(err.to_json()["path"], json!(["updateTypeTest"]));
+    let count: i64 = data
+        .pool
+        .get()
+        .await
+        .unwrap()
+        .query_one(
+            "SELECT count(*) FROM mutation_test WHERE name = 'rolled back'",
+            &[],
+        )
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(count, 0);
+}
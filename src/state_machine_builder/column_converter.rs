@@ -1,87 +1,228 @@
-use crate::build_schema::PostgresType;
-use chrono::{DateTime, Utc};
+use crate::build_schema::{PostgresEnum, PostgresType};
+use crate::settings::{FeatureSettings, TimestampPrecision};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use postgres::types::{FromSql, Type};
 use postgres::Row;
+use std::error::Error;
+
+//how the values of columns are written, as configured by FeatureSettings
+#[derive(Clone, Copy, Debug)]
+pub struct ValueFormat {
+    pub big_numbers_as_strings: bool,
+    pub timestamp_precision: TimestampPrecision,
+}
+
+//the undecoded bytes of any column (None if NULL). Used to compare primary keys of any type
+//without knowing how to decode them
+pub struct RawColumn<'a>(pub Option<&'a [u8]>);
+
+impl<'a> FromSql<'a> for RawColumn<'a> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(RawColumn(Some(raw)))
+    }
+    fn from_sql_null(_: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(RawColumn(None))
+    }
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
 
 impl PostgresType {
-    pub fn stringify_column(&self, row: &Row, index: usize) -> String {
-        match self {
+    //the JSON of the column at index, which was selected as described by select_column
+    pub fn stringify_column(
+        &self,
+        row: &Row,
+        index: usize,
+        format: ValueFormat,
+    ) -> Result<String, postgres::Error> {
+        Ok(match self {
+            PostgresType::Nullable(inner) => {
+                let raw: RawColumn = row.try_get(index)?;
+                match raw.0 {
+                    Some(_) => inner.stringify_column(row, index, format)?,
+                    None => String::from("null"),
+                }
+            }
             PostgresType::Int => {
-                let col_val: i32 = row.get(index);
+                let col_val: i32 = row.try_get(index)?;
                 col_val.to_string()
             }
-            PostgresType::Str => {
-                let col_val: &str = row.get(index);
-                serde_json::to_string(col_val).unwrap()
+            PostgresType::SmallInt => {
+                let col_val: i16 = row.try_get(index)?;
+                col_val.to_string()
+            }
+            PostgresType::BigInt | PostgresType::Numeric => {
+                let col_val: &str = row.try_get(index)?;
+                format.big_number(col_val)
             }
             PostgresType::Float => {
-                let col_val: f64 = row.get(index);
+                let col_val: f64 = row.try_get(index)?;
+                col_val.to_string()
+            }
+            PostgresType::Real => {
+                let col_val: f32 = row.try_get(index)?;
                 col_val.to_string()
             }
             PostgresType::Timestamp => {
-                let col_val: chrono::NaiveDateTime = row.get(index);
-                ["\"", &col_val.to_string(), "\""].concat()
+                let col_val: NaiveDateTime = row.try_get(index)?;
+                ["\"", &format.timestamp(col_val), "\""].concat()
             }
             PostgresType::Timestamptz => {
-                let col_val: DateTime<Utc> = row.get(index);
-                ["\"", &col_val.to_string(), "\""].concat()
+                let col_val: DateTime<Utc> = row.try_get(index)?;
+                ["\"", &format.timestamptz(col_val), "\""].concat()
             }
             PostgresType::Boolean => {
-                let col_val: bool = row.get(index);
+                let col_val: bool = row.try_get(index)?;
                 col_val.to_string()
             }
             PostgresType::Json => {
-                let col_val: serde_json::Value = row.get(index);
+                let col_val: serde_json::Value = row.try_get(index)?;
                 col_val.to_string()
             }
-            PostgresType::NullableInt => {
-                let col_val: Option<i32> = row.get(index);
-                match col_val {
-                    Some(val) => val.to_string(),
-                    None => String::from("null"),
-                }
+            PostgresType::Array(_) | PostgresType::Composite(_) => {
+                let col_val: serde_json::Value = row.try_get(index)?;
+                let mut s = String::new();
+                write_json(&mut s, &col_val, self, format);
+                s
             }
-            PostgresType::NullableStr => {
-                let col_val: Option<&str> = row.get(index);
-                match col_val {
-                    Some(val) => serde_json::to_string(val).unwrap(),
-                    None => String::from("null"),
-                }
+            PostgresType::Enum(_) => {
+                let col_val: &str = row.try_get(index)?;
+                ["\"", &PostgresEnum::value_name(col_val), "\""].concat()
             }
-            PostgresType::NullableFloat => {
-                let col_val: Option<f64> = row.get(index);
-                match col_val {
-                    Some(val) => val.to_string(),
-                    None => String::from("null"),
-                }
+            PostgresType::Timetz => {
+                let col_val: &str = row.try_get(index)?;
+                ["\"", &rfc_3339_time(col_val), "\""].concat()
             }
-            PostgresType::NullableTimestamp => {
-                let col_val: Option<chrono::NaiveDateTime> = row.get(index);
-                match col_val {
-                    Some(val) => ["\"", &val.to_string(), "\""].concat(),
-                    None => String::from("null"),
-                }
+            //strings, and the types that are selected as text (see select_column)
+            _ => {
+                let col_val: &str = row.try_get(index)?;
+                serde_json::to_string(col_val).unwrap_or_default()
             }
-            PostgresType::NullableTimestamptz => {
-                let col_val: Option<DateTime<Utc>> = row.get(index);
-                match col_val {
-                    Some(val) => ["\"", &val.to_string(), "\""].concat(),
-                    None => String::from("null"),
+        })
+    }
+}
+
+//writes a JSON value of pg_type (an array or composite selected as JSON), converting the values
+//that are written differently from how Postgres' to_json writes them: enum labels and big numbers
+//(which json_value selects as text)
+fn write_json(
+    s: &mut String,
+    value: &serde_json::Value,
+    pg_type: &PostgresType,
+    format: ValueFormat,
+) {
+    match (value, pg_type) {
+        (value, PostgresType::Nullable(inner)) => write_json(s, value, inner, format),
+        (serde_json::Value::String(label), PostgresType::Enum(_)) => {
+            s.push_str(&serde_json::Value::from(PostgresEnum::value_name(label)).to_string())
+        }
+        (serde_json::Value::String(text), PostgresType::BigInt | PostgresType::Numeric) => {
+            s.push_str(&format.big_number(text))
+        }
+        (serde_json::Value::Array(items), PostgresType::Array(element)) => {
+            s.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
                 }
+                write_json(s, item, element, format);
             }
-            PostgresType::NullableBoolean => {
-                let col_val: Option<bool> = row.get(index);
-                match col_val {
-                    Some(val) => val.to_string(),
-                    None => String::from("null"),
+            s.push(']');
+        }
+        //written in the order of the composite's fields, which is the order they were selected in
+        (serde_json::Value::Object(object), PostgresType::Composite(composite)) => {
+            s.push('{');
+            for (i, (key, _, field_type)) in composite.fields.iter().enumerate() {
+                if i > 0 {
+                    s.push(',');
                 }
-            }
-            PostgresType::NullableJson => {
-                let col_val: Option<serde_json::Value> = row.get(index);
-                match col_val {
-                    Some(val) => val.to_string(),
-                    None => String::from("null"),
+                s.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                s.push(':');
+                match object.get(key) {
+                    Some(field_value) => write_json(s, field_value, field_type, format),
+                    None => s.push_str("null"),
                 }
             }
+            s.push('}');
+        }
+        //to_json writes timestamps in ISO 8601, but not necessarily with the configured precision
+        (serde_json::Value::String(text), PostgresType::Timestamp) => {
+            match NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f") {
+                Ok(timestamp) => s.push_str(&["\"", &format.timestamp(timestamp), "\""].concat()),
+                Err(_) => s.push_str(&value.to_string()),
+            }
+        }
+        (serde_json::Value::String(text), PostgresType::Timestamptz) => {
+            match DateTime::parse_from_rfc3339(text) {
+                Ok(timestamp) => s.push_str(
+                    &[
+                        "\"",
+                        &format.timestamptz(timestamp.with_timezone(&Utc)),
+                        "\"",
+                    ]
+                    .concat(),
+                ),
+                Err(_) => s.push_str(&value.to_string()),
+            }
+        }
+        (serde_json::Value::String(text), PostgresType::Timetz) => {
+            s.push_str(&["\"", &rfc_3339_time(text), "\""].concat())
+        }
+        (value, _) => s.push_str(&value.to_string()),
+    }
+}
+
+//Postgres writes the offsets of times with a time zone without their minutes if they are whole
+//hours (e.g 10:00:00+02), which RFC 3339 requires
+fn rfc_3339_time(time: &str) -> String {
+    match time.rfind(['+', '-']) {
+        Some(sign) if time.len() - sign == 3 => [time, ":00"].concat(),
+        _ => time.to_string(),
+    }
+}
+
+impl ValueFormat {
+    pub fn new(features: &FeatureSettings) -> ValueFormat {
+        ValueFormat {
+            big_numbers_as_strings: features.big_numbers_as_strings,
+            timestamp_precision: features.timestamp_precision,
+        }
+    }
+
+    //the text of an int8 or numeric as a JSON string, or as a JSON number if asked to (and it
+    //is finite, as JSON has no NaN or Infinity)
+    fn big_number(&self, text: &str) -> String {
+        if !self.big_numbers_as_strings && text.parse::<f64>().is_ok_and(f64::is_finite) {
+            text.to_string()
+        } else {
+            ["\"", text, "\""].concat()
+        }
+    }
+
+    //RFC 3339 without an offset, as the timestamp isn't in any particular time zone
+    fn timestamp(&self, timestamp: NaiveDateTime) -> String {
+        let fraction = match self.timestamp_precision {
+            TimestampPrecision::Auto => "%.f",
+            TimestampPrecision::Seconds => "",
+            TimestampPrecision::Milliseconds => "%.3f",
+            TimestampPrecision::Microseconds => "%.6f",
+        };
+        timestamp
+            .format(&["%Y-%m-%dT%H:%M:%S", fraction].concat())
+            .to_string()
+    }
+
+    fn timestamptz(&self, timestamp: DateTime<Utc>) -> String {
+        [&self.timestamp(timestamp.naive_utc()), "Z"].concat()
+    }
+}
+
+impl Default for ValueFormat {
+    fn default() -> Self {
+        ValueFormat {
+            big_numbers_as_strings: true,
+            timestamp_precision: TimestampPrecision::Auto,
         }
     }
 }
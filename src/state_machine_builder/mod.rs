@@ -1,5 +1,9 @@
+use crate::error::{ErrorKind, GraphQLError};
 use crate::generate_sql::*;
-use core::slice::Iter;
+use column_converter::RawColumn;
+pub use column_converter::ValueFormat;
+use std::collections::HashMap;
+use std::ops::Range;
 use tokio_postgres::Row;
 mod column_converter;
 
@@ -7,73 +11,272 @@ mod column_converter;
 #[path = "./test.rs"]
 mod test;
 
-#[derive(Debug)]
-pub enum State {
-    Parent,
-    ForeignField(usize),
+//writes the response data object, one root field at a time
+pub struct JsonBuilder {
+    s: String,
 }
-
-pub struct JsonBuilder<'a> {
-    pub s: String,
-    row_iter: Iter<'a, Row>,
-    state: State,
-    table_metadata: Vec<TableMetadata>,
-    root_key_name: &'a str,
+impl Default for JsonBuilder {
+    fn default() -> Self {
+        JsonBuilder::new()
+    }
 }
-impl<'a> JsonBuilder<'a> {
-    pub fn new(
-        row_iter: Iter<'a, Row>,
-        table_metadata: Vec<TableMetadata>,
-        root_key_name: &'a str,
-    ) -> Self {
+impl JsonBuilder {
+    pub fn new() -> Self {
         JsonBuilder {
-            s: String::new(),
-            row_iter,
-            state: State::Parent,
-            table_metadata,
-            root_key_name,
+            s: String::from("{"),
+        }
+    }
+
+    //writes the root field described by ctx, using the rows returned by its SQL query
+    pub fn add_root_field(
+        &mut self,
+        ctx: &JsonBuilderContext,
+        rows: &[Row],
+    ) -> Result<(), GraphQLError> {
+        let rows: Vec<&Row> = rows.iter().collect();
+        let table_metadata = &ctx.table_metadata;
+        self.s.push_str(&["\"", &ctx.root_key_name, "\":"].concat());
+        if let Some(connection) = &ctx.connection {
+            write_connection(&mut self.s, ctx, connection, &rows)?;
+            self.s.push(',');
+            return Ok(());
+        }
+        let groups = group_rows(&rows, &table_metadata[0].primary_key_range);
+        if ctx.root_query_is_many {
+            write_array(&mut self.s, ctx, 0, &groups)?;
+        } else {
+            match groups.first() {
+                Some(rows) => write_object(&mut self.s, ctx, 0, rows)?,
+                None => self.s.push_str("null"),
+            }
+        }
+        self.s.push(',');
+        Ok(())
+    }
+
+    //writes a root field whose value didn't need to be queried
+    pub fn add_resolved_field(&mut self, key: &str, value: &serde_json::Value) {
+        self.s.push_str(&["\"", key, "\":"].concat());
+        self.s.push_str(&value.to_string());
+        self.s.push(',');
+    }
+
+    pub fn finish(mut self) -> String {
+        if self.s.ends_with(',') {
+            self.s.pop();
         }
+        self.s.push('}');
+        self.s
     }
+}
+
+//every row in rows shares the same primary key for the table at table_index (they are the
+//flattened LEFT JOIN rows of one object), so terminal fields can be read from any of them
+fn write_object(
+    s: &mut String,
+    ctx: &JsonBuilderContext,
+    table_index: usize,
+    rows: &[&Row],
+) -> Result<(), GraphQLError> {
+    let table_metadata = &ctx.table_metadata;
+    let metadata = &table_metadata[table_index];
+    //terminal columns come directly after the primary keys of this table
+    let mut column = metadata.primary_key_range.end;
+    s.push('{');
+    for field in &metadata.graphql_fields {
+        match field {
+            ColumnInfo::Terminal(field_name, pg_type) => {
+                s.push_str(&["\"", field_name, "\":"].concat());
+                s.push_str(&pg_type.stringify_column(rows[0], column, ctx.value_format)?);
+                column += 1;
+            }
+            ColumnInfo::Foreign(field_name, child_index) => {
+                s.push_str(&["\"", field_name, "\":"].concat());
+                let groups = group_rows(rows, &table_metadata[*child_index].primary_key_range);
+                write_array(s, ctx, *child_index, &groups)?;
+            }
+            ColumnInfo::Typename(field_name, type_name) => {
+                s.push_str(&["\"", field_name, "\":\"", type_name, "\""].concat());
+            }
+            ColumnInfo::NodeId(field_name) => {
+                let node_id = base64::encode(rows[0].try_get::<usize, &str>(column)?);
+                s.push_str(&["\"", field_name, "\":\"", &node_id, "\""].concat());
+                column += 1;
+            }
+            ColumnInfo::ForeignSingular(field_name, child_index) => {
+                s.push_str(&["\"", field_name, "\":"].concat());
+                //all rows refer to the same parent, unless the parent's primary keys are NULL
+                //(no match was found by the LEFT JOIN)
+                let groups = group_rows(rows, &table_metadata[*child_index].primary_key_range);
+                match groups.first() {
+                    Some(rows) => write_object(s, ctx, *child_index, rows)?,
+                    None => s.push_str("null"),
+                }
+            }
+        }
+        s.push(',');
+    }
+    if s.ends_with(',') {
+        s.pop();
+    }
+    s.push('}');
+    Ok(())
+}
 
-    pub fn exec_until_state_change(&mut self) {
-        self.s.push_str(&["{\"", self.root_key_name, "\":["].concat());
-        for row in self.row_iter.by_ref() {
-            match self.state {
-                State::Parent => {
-                    self.s.push('{');
-                    for (index, col) in self
-                        .table_metadata
-                        .get(0)
-                        .unwrap()
-                        .graphql_fields
-                        .iter()
-                        .enumerate()
-                    {
-                        match col {
-                            ColumnInfo::Terminal(col_name, pg_type) => self.s.push_str(
-                                &[
-                                    "\"",
-                                    col_name,
-                                    "\":",
-                                    &pg_type.stringify_column(row, index + 1),
-                                    ",",
-                                ]
-                                .concat(),
-                            ),
-                            ColumnInfo::Foreign(field_name) => {
-                                self.s.push_str(&[field_name, ":{"].concat());
-                                self.state = State::ForeignField(1)
+//the rows of a connection are the flattened rows of its nodes, or a single row of NULL nodes if the
+//page is empty. The nodes are grouped by their cursors, which are unique as they include the
+//primary keys
+fn write_connection(
+    s: &mut String,
+    ctx: &JsonBuilderContext,
+    connection: &ConnectionMetadata,
+    rows: &[&Row],
+) -> Result<(), GraphQLError> {
+    let cursor_column = connection.cursor_column;
+    let groups = group_rows(rows, &(cursor_column..cursor_column + 1));
+    let cursor = |rows: &[&Row]| -> Result<String, GraphQLError> {
+        let cursor = rows[0].try_get::<usize, &str>(cursor_column)?;
+        Ok(["\"", &base64::encode(cursor), "\""].concat())
+    };
+    let first_cursor = groups.first().map(|rows| cursor(rows)).transpose()?;
+    let last_cursor = groups.last().map(|rows| cursor(rows)).transpose()?;
+    //the totalCount and pageInfo are repeated on every row
+    let first_row = rows.first().ok_or_else(|| {
+        GraphQLError::from(ErrorKind::Database(String::from(
+            "The query of the connection returned no rows",
+        )))
+    })?;
+    s.push('{');
+    for field in &connection.fields {
+        match field {
+            ConnectionField::TotalCount(key) => {
+                let total_count = first_row.try_get::<usize, i64>(cursor_column + 1)?;
+                s.push_str(&["\"", key, "\":", &total_count.to_string()].concat());
+            }
+            ConnectionField::Nodes(key, table_index) => {
+                s.push_str(&["\"", key, "\":"].concat());
+                write_array(s, ctx, *table_index, &groups)?;
+            }
+            ConnectionField::Edges(key, edge_fields) => {
+                s.push_str(&["\"", key, "\":["].concat());
+                for rows in &groups {
+                    s.push('{');
+                    for edge_field in edge_fields {
+                        match edge_field {
+                            EdgeField::Cursor(key) => {
+                                s.push_str(&["\"", key, "\":", &cursor(rows)?].concat())
+                            }
+                            EdgeField::Node(key, table_index) => {
+                                s.push_str(&["\"", key, "\":"].concat());
+                                write_object(s, ctx, *table_index, rows)?;
+                            }
+                            EdgeField::Typename(key, type_name) => {
+                                s.push_str(&["\"", key, "\":\"", type_name, "\""].concat())
                             }
-                            ColumnInfo::ForeignSingular(_) => unimplemented!(),
                         }
+                        s.push(',');
+                    }
+                    if s.ends_with(',') {
+                        s.pop();
                     }
-                    self.s.pop();
-                    self.s.push_str("},");
+                    s.push_str("},");
+                }
+                if s.ends_with(',') {
+                    s.pop();
                 }
-                State::ForeignField(_) => unimplemented!(),
+                s.push(']');
+            }
+            ConnectionField::PageInfo(key, page_info_fields) => {
+                s.push_str(&["\"", key, "\":{"].concat());
+                for page_info_field in page_info_fields {
+                    let (key, value) = match page_info_field {
+                        PageInfoField::HasNextPage(key) => (
+                            key,
+                            first_row
+                                .try_get::<usize, bool>(cursor_column + 2)?
+                                .to_string(),
+                        ),
+                        PageInfoField::HasPreviousPage(key) => (
+                            key,
+                            first_row
+                                .try_get::<usize, bool>(cursor_column + 3)?
+                                .to_string(),
+                        ),
+                        PageInfoField::StartCursor(key) => (
+                            key,
+                            first_cursor.clone().unwrap_or_else(|| String::from("null")),
+                        ),
+                        PageInfoField::EndCursor(key) => (
+                            key,
+                            last_cursor.clone().unwrap_or_else(|| String::from("null")),
+                        ),
+                        PageInfoField::Typename(key, type_name) => {
+                            (key, ["\"", type_name, "\""].concat())
+                        }
+                    };
+                    s.push_str(&["\"", key, "\":", &value, ","].concat());
+                }
+                if s.ends_with(',') {
+                    s.pop();
+                }
+                s.push('}');
+            }
+            ConnectionField::Typename(key, type_name) => {
+                s.push_str(&["\"", key, "\":\"", type_name, "\""].concat());
+            }
+        }
+        s.push(',');
+    }
+    if s.ends_with(',') {
+        s.pop();
+    }
+    s.push('}');
+    Ok(())
+}
+
+fn write_array(
+    s: &mut String,
+    ctx: &JsonBuilderContext,
+    table_index: usize,
+    groups: &[Vec<&Row>],
+) -> Result<(), GraphQLError> {
+    s.push('[');
+    for rows in groups {
+        write_object(s, ctx, table_index, rows)?;
+        s.push(',');
+    }
+    if s.ends_with(',') {
+        s.pop();
+    }
+    s.push(']');
+    Ok(())
+}
+
+//splits rows into one group per distinct primary key of a table, in order of first appearance.
+//Rows where every primary key column is NULL are skipped, as that is how a LEFT JOIN reports that
+//there was no matching child. Tables without primary keys can't be grouped, so every row is its
+//own object
+fn group_rows<'r>(rows: &[&'r Row], primary_key_range: &Range<usize>) -> Vec<Vec<&'r Row>> {
+    if primary_key_range.is_empty() {
+        return rows.iter().map(|row| vec![*row]).collect();
+    }
+    let mut groups: Vec<Vec<&Row>> = vec![];
+    let mut group_indices: HashMap<Vec<Option<&[u8]>>, usize> = HashMap::new();
+    for row in rows {
+        let key = primary_key_range
+            .clone()
+            .map(|index| row.get::<usize, RawColumn>(index).0)
+            .collect::<Vec<Option<&[u8]>>>();
+        if key.iter().all(Option::is_none) {
+            continue;
+        }
+        match group_indices.get(&key) {
+            Some(group_index) => groups[*group_index].push(row),
+            None => {
+                group_indices.insert(key, groups.len());
+                groups.push(vec![row]);
             }
         }
-        self.s.pop();
-        self.s.push_str("]}");
     }
+    groups
 }
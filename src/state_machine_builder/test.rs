@@ -1,16 +1,43 @@
-use std::collections::HashSet;
 use super::*;
-use tokio_postgres::Client;
-use serde_json::Value;
 use crate::build_schema::get_schema_and_client;
+use crate::settings::TimestampPrecision;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use tokio_postgres::Client;
 async fn convert_gql(gql_query: &str) -> (GraphQLSchema, Client, Value) {
     let (schema, client) = get_schema_and_client().await;
-    let ctx = schema.parse_graphql(gql_query).unwrap();
-    println!("{}", ctx.sql_query);
-    let rows = client.query(&ctx.sql_query, &[]).await.unwrap();
-    let mut builder = JsonBuilder::new(rows.iter(), ctx.table_metadata, &ctx.root_key_name);
-    builder.exec_until_state_change();
-    (schema, client, serde_json::from_str(&*&builder.s).unwrap())
+    let value = run_gql(&schema, &client, gql_query).await;
+    (schema, client, value)
+}
+
+//runs a mutation in a transaction that is rolled back, so that tests running at the same time never
+//see its changes
+async fn convert_gql_rolled_back(gql_query: &str) -> Value {
+    let (schema, client) = get_schema_and_client().await;
+    client.batch_execute("BEGIN").await.unwrap();
+    let value = run_gql(&schema, &client, gql_query).await;
+    client.batch_execute("ROLLBACK").await.unwrap();
+    value
+}
+
+async fn run_gql(schema: &GraphQLSchema, client: &Client, gql_query: &str) -> Value {
+    let mut builder = JsonBuilder::new();
+    for root_field in schema.parse_graphql(gql_query, &Map::new(), None).unwrap() {
+        let ctx = match root_field {
+            RootField::Sql(ctx) => ctx,
+            RootField::Introspection(key, value) => {
+                builder.add_resolved_field(&key, &value);
+                continue;
+            }
+        };
+        println!("{}", ctx.sql_query);
+        let rows = client
+            .query(&ctx.sql_query, &ctx.sql_params())
+            .await
+            .unwrap();
+        builder.add_root_field(&ctx, &rows).unwrap();
+    }
+    serde_json::from_str(&builder.finish()).unwrap()
 }
 
 #[actix_rt::test]
@@ -70,7 +97,7 @@ async fn test_random_user() {
         .await
     {
         Ok(user_query) => {
-            let user_row = user_query.get(0).unwrap();
+            let user_row = user_query.first().unwrap();
 
             assert_eq!(
                 user.get("reputation").unwrap(),
@@ -93,7 +120,7 @@ async fn test_random_user() {
         )
         .await
         .unwrap()
-        .get(0)
+        .first()
         .unwrap()
         .get(0);
     assert_eq!(
@@ -116,3 +143,947 @@ async fn test_random_user() {
         );
     }
 }
+
+#[actix_rt::test]
+async fn nested_one_to_many() {
+    let gql_query = "
+        query{
+          siteUsers{
+            id
+            postsByOwneruserid{
+              id
+              commentsByPostid{
+                id
+              }
+            }
+            badgesByUserid{
+              id
+            }
+          }
+        }";
+
+    let (_, client, p) = convert_gql(gql_query).await;
+    let site_users = p.get("siteUsers").unwrap().as_array().unwrap();
+    let user_count: i64 = client
+        .query("SELECT count(*) FROM site_user", &[])
+        .await
+        .unwrap()
+        .first()
+        .unwrap()
+        .get(0);
+    assert_eq!(site_users.len(), user_count as usize);
+
+    for user in site_users {
+        let user_id = user.get("id").unwrap().as_i64().unwrap() as i32;
+        //sibling joins multiply the rows, so check that grouping removed the duplicates
+        for (field, sql) in [
+            (
+                "postsByOwneruserid",
+                "SELECT count(*) FROM post where owneruserid = $1",
+            ),
+            (
+                "badgesByUserid",
+                "SELECT count(*) FROM badge where userid = $1",
+            ),
+        ] {
+            let count: i64 = client
+                .query(sql, &[&user_id])
+                .await
+                .unwrap()
+                .first()
+                .unwrap()
+                .get(0);
+            assert_eq!(
+                user.get(field).unwrap().as_array().unwrap().len(),
+                count as usize
+            );
+        }
+        for post in user.get("postsByOwneruserid").unwrap().as_array().unwrap() {
+            let post_id = post.get("id").unwrap().as_i64().unwrap() as i32;
+            let count: i64 = client
+                .query(
+                    "SELECT count(*) FROM comment where postid = $1",
+                    &[&post_id],
+                )
+                .await
+                .unwrap()
+                .first()
+                .unwrap()
+                .get(0);
+            assert_eq!(
+                post.get("commentsByPostid")
+                    .unwrap()
+                    .as_array()
+                    .unwrap()
+                    .len(),
+                count as usize
+            );
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn many_to_one() {
+    let gql_query = "
+        query{
+          posts{
+            id
+            owneruserid
+            siteUserByOwneruserid{
+              id
+              badgesByUserid{
+                userid
+              }
+            }
+          }
+        }";
+
+    let (_, _, p) = convert_gql(gql_query).await;
+    let posts = p.get("posts").unwrap().as_array().unwrap();
+    assert!(posts
+        .iter()
+        .any(|post| post.get("siteUserByOwneruserid").unwrap().is_null()));
+    for post in posts {
+        let owner = post.get("siteUserByOwneruserid").unwrap();
+        if post.get("owneruserid").unwrap().is_null() {
+            assert!(owner.is_null());
+        } else {
+            assert_eq!(owner.get("id").unwrap(), post.get("owneruserid").unwrap());
+            for badge in owner.get("badgesByUserid").unwrap().as_array().unwrap() {
+                assert_eq!(badge.get("userid").unwrap(), owner.get("id").unwrap());
+            }
+        }
+    }
+}
+
+#[actix_rt::test]
+async fn aliases() {
+    let gql_query = "
+        query{
+          first: siteUser(id: 1){
+            userId: id
+            name: displayname
+            posts: postsByOwneruserid{
+              id
+              author: siteUserByOwneruserid{
+                id
+              }
+            }
+          }
+          second: siteUser(id: 2){
+            id
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let first = p.get("first").unwrap();
+    assert_eq!(first.get("userId").unwrap(), 1);
+    assert_eq!(first.get("name").unwrap(), "user1");
+    for post in first.get("posts").unwrap().as_array().unwrap() {
+        assert_eq!(post.get("author").unwrap().get("id").unwrap(), 1);
+    }
+    assert_eq!(p.get("second").unwrap().get("id").unwrap(), 2);
+}
+
+#[actix_rt::test]
+async fn fragments() {
+    let gql_query = "
+        query{
+          siteUser(id: 1){
+            id
+            ...UserFields
+            postsByOwneruserid{
+              ... on Post {
+                title
+              }
+            }
+          }
+        }
+        fragment UserFields on SiteUser {
+          id
+          displayname
+          postsByOwneruserid{
+            ...PostFields
+          }
+        }
+        fragment PostFields on Post {
+          id
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let user = p.get("siteUser").unwrap().as_object().unwrap();
+    assert_eq!(
+        user.keys().collect::<Vec<&String>>(),
+        vec!["displayname", "id", "postsByOwneruserid"]
+    );
+    for post in user.get("postsByOwneruserid").unwrap().as_array().unwrap() {
+        assert_eq!(post.as_object().unwrap().len(), 2);
+        post.get("id").unwrap().as_i64().unwrap();
+        post.get("title").unwrap().as_str().unwrap();
+    }
+}
+
+#[actix_rt::test]
+async fn typename() {
+    let gql_query = "
+        query{
+          __typename
+          siteUser(id: 1){
+            __typename
+            id
+            posts: postsByOwneruserid{
+              type: __typename
+            }
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    assert_eq!(p.get("__typename").unwrap(), "Query");
+    let user = p.get("siteUser").unwrap();
+    assert_eq!(user.get("__typename").unwrap(), "SiteUser");
+    assert_eq!(user.get("id").unwrap(), 1);
+    for post in user.get("posts").unwrap().as_array().unwrap() {
+        assert_eq!(post.get("type").unwrap(), "Post");
+    }
+}
+
+#[actix_rt::test]
+async fn postgres_types() {
+    let gql_query = "
+        query {
+          typeTests {
+            id small big single amount code uid day atTime zonedTime duration bytes address
+            network price document search email mood tags scores moods docs
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let type_tests = p["typeTests"].as_array().unwrap();
+    let find_row = |id: i64| type_tests.iter().find(|row| row["id"] == id).unwrap();
+    assert_eq!(
+        find_row(1),
+        &json!({
+            "id": 1,
+            "small": 1,
+            "big": "9007199254740993",
+            "single": 1.5,
+            "amount": "12.34",
+            "code": "abc",
+            "uid": "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+            "day": "2020-01-02",
+            "atTime": "12:34:56",
+            "zonedTime": "12:34:56+05:30",
+            "duration": "1 day 02:00:00",
+            "bytes": "\\x0102",
+            "address": "192.168.0.1/32",
+            "network": "10.0.0.0/8",
+            "price": "$1.50",
+            "document": "<a>b</a>",
+            //tsvector isn't mapped, so it falls back to its text representation
+            "search": "'cat' 'fat'",
+            "email": "A@Example.com",
+            "mood": "SO_SO",
+            "tags": ["a", "b,c", null],
+            "scores": [1, 2, 3],
+            "moods": ["HAPPY", "SO_SO"],
+            "docs": [{ "x": 1 }],
+        })
+    );
+    let nulls = find_row(2).as_object().unwrap();
+    assert!(nulls
+        .iter()
+        .filter(|(key, _)| !["id", "small", "big", "scores"].contains(&key.as_str()))
+        .all(|(_, value)| value.is_null()));
+    assert_eq!(nulls["scores"], json!([]));
+}
+
+#[actix_rt::test]
+async fn filter_by_postgres_types() {
+    let gql_query = "
+        query {
+          typeTests(where: {
+            big: { eq: 9007199254740993 }, uid: { eq: \"a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11\" },
+            day: { eq: \"2020-01-02\" }, address: { eq: \"192.168.0.1\" },
+            email: { eq: \"a@example.COM\" }, mood: { eq: SO_SO }
+          }) {
+            id
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    assert_eq!(p["typeTests"], json!([{ "id": 1 }]));
+}
+
+#[actix_rt::test]
+async fn enum_mutations() {
+    let gql_query = "
+        mutation {
+          updateTypeTest(id: 2, patch: { mood: HAPPY }) {
+            mood
+          }
+        }";
+    let p = convert_gql_rolled_back(gql_query).await;
+    assert_eq!(p["updateTypeTest"], json!({ "mood": "HAPPY" }));
+}
+
+#[actix_rt::test]
+async fn time_zones() {
+    let gql_query = "
+        mutation {
+          updateTypeTest(id: 2, patch: { atTime: \"10:00:00+02:00\", zonedTime: \"10:00:00+02:00\" }) {
+            atTime
+            zonedTime
+          }
+        }";
+    let p = convert_gql_rolled_back(gql_query).await;
+    //the offset is only kept by timetz
+    assert_eq!(
+        p["updateTypeTest"],
+        json!({ "atTime": "10:00:00", "zonedTime": "10:00:00+02:00" })
+    );
+}
+
+#[actix_rt::test]
+async fn arrays() {
+    let gql_query = "
+        query {
+          contains: typeTests(where: { scores: { contains: [3, 1] }, tags: { overlaps: \"b,c\" } }) {
+            id
+          }
+          containedBy: typeTests(where: { scores: { containedBy: [1, 2, 3, 4] } }) {
+            id
+          }
+          eq: typeTests(where: { moods: { eq: [HAPPY, SO_SO] } }) {
+            id
+          }
+          none: typeTests(where: { scores: { overlaps: [4, 5] } }) {
+            id
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    assert_eq!(p["contains"], json!([{ "id": 1 }]));
+    //the empty array of the second row is contained by every array
+    assert_eq!(p["containedBy"].as_array().unwrap().len(), 2);
+    assert_eq!(p["eq"], json!([{ "id": 1 }]));
+    assert_eq!(p["none"], json!([]));
+
+    let gql_query = "
+        mutation {
+          updateTypeTest(id: 2, patch: { tags: [\"\\\"quoted\\\"\", null, \"NULL\"], scores: [] }) {
+            tags
+            scores
+          }
+        }";
+    let p = convert_gql_rolled_back(gql_query).await;
+    assert_eq!(
+        p["updateTypeTest"],
+        json!({ "tags": ["\"quoted\"", null, "NULL"], "scores": [] })
+    );
+}
+
+#[actix_rt::test]
+async fn domains_and_composites() {
+    let gql_query = "
+        query {
+          typeTests {
+            id
+            contact
+            rating
+            home { street town: city moods __typename }
+            spot { label address { street city } }
+            history { street }
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let type_tests = p["typeTests"].as_array().unwrap();
+    let find_row = |id: i64| type_tests.iter().find(|row| row["id"] == id).unwrap();
+    assert_eq!(
+        find_row(1),
+        &json!({
+            "id": 1,
+            "contact": "a@b.c",
+            "rating": 1,
+            "home": {
+                "street": "1 Main St",
+                "town": "Springfield",
+                "moods": ["SAD"],
+                "__typename": "Address",
+            },
+            "spot": { "label": "work", "address": { "street": "2 Side St", "city": null } },
+            "history": [{ "street": "old st" }],
+        })
+    );
+    assert_eq!(
+        find_row(2),
+        &json!({
+            "id": 2,
+            "contact": null,
+            "rating": 1,
+            "home": null,
+            "spot": null,
+            "history": null,
+        })
+    );
+
+    let gql_query = "
+        mutation {
+          updateTypeTest(id: 2, patch: {
+            home: { street: \"\\\"quoted\\\", (parens)\", moods: [HAPPY, SO_SO] },
+            history: [{ city: \"c\" }, { street: null }]
+          }) {
+            home { street city moods }
+            history { street city }
+          }
+        }";
+    let p = convert_gql_rolled_back(gql_query).await;
+    assert_eq!(
+        p["updateTypeTest"],
+        json!({
+            "home": { "street": "\"quoted\", (parens)", "city": null, "moods": ["HAPPY", "SO_SO"] },
+            //a composite with only NULL attributes is still a value
+            "history": [{ "street": null, "city": "c" }, { "street": null, "city": null }],
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn big_numbers() {
+    let gql_query = "
+        query {
+          typeTest(id: 1) {
+            big amount precise bigs
+          }
+        }";
+    let (mut schema, client, p) = convert_gql(gql_query).await;
+    assert_eq!(
+        p["typeTest"],
+        json!({
+            "big": "9007199254740993",
+            "amount": "12.34",
+            "precise": "12345678901234567890.123456789",
+            "bigs": ["9007199254740993", null],
+        })
+    );
+
+    //as numbers, the exact digits are still written, even though serde_json can't parse them
+    //back without rounding
+    schema.value_format.big_numbers_as_strings = false;
+    let mut builder = JsonBuilder::new();
+    for root_field in schema.parse_graphql(gql_query, &Map::new(), None).unwrap() {
+        if let RootField::Sql(ctx) = root_field {
+            let rows = client
+                .query(&ctx.sql_query, &ctx.sql_params())
+                .await
+                .unwrap();
+            builder.add_root_field(&ctx, &rows).unwrap();
+        }
+    }
+    assert_eq!(
+        builder.finish(),
+        "{\"typeTest\":{\"big\":9007199254740993,\"amount\":12.34,\"precise\":12345678901234567890.123456789,\"bigs\":[9007199254740993,null]}}"
+    );
+}
+
+#[actix_rt::test]
+async fn rfc_3339_timestamps() {
+    let gql_query = "
+        query {
+          typeTest(id: 1) {
+            happened moments
+          }
+          siteUser(id: 1) {
+            creationdate
+          }
+        }";
+    let (mut schema, client, p) = convert_gql(gql_query).await;
+    assert_eq!(
+        p,
+        json!({
+            "typeTest": {
+                "happened": "2021-01-01T08:00:00.500Z",
+                "moments": ["2021-01-01T10:00:00.123456"],
+            },
+            "siteUser": { "creationdate": "2020-01-02T00:00:00" },
+        })
+    );
+
+    for (precision, happened, moment) in [
+        (
+            TimestampPrecision::Seconds,
+            "2021-01-01T08:00:00Z",
+            "2021-01-01T10:00:00",
+        ),
+        (
+            TimestampPrecision::Milliseconds,
+            "2021-01-01T08:00:00.500Z",
+            "2021-01-01T10:00:00.123",
+        ),
+        (
+            TimestampPrecision::Microseconds,
+            "2021-01-01T08:00:00.500000Z",
+            "2021-01-01T10:00:00.123456",
+        ),
+    ] {
+        schema.value_format.timestamp_precision = precision;
+        let ctx = match schema
+            .parse_graphql(gql_query, &Map::new(), None)
+            .unwrap()
+            .remove(0)
+        {
+            RootField::Sql(ctx) => ctx,
+            RootField::Introspection(key, _) => panic!("{} wasn't resolved with SQL", key),
+        };
+        let rows = client
+            .query(&ctx.sql_query, &ctx.sql_params())
+            .await
+            .unwrap();
+        let mut builder = JsonBuilder::new();
+        builder.add_root_field(&ctx, &rows).unwrap();
+        let p: Value = serde_json::from_str(&builder.finish()).unwrap();
+        assert_eq!(p["typeTest"]["happened"], happened);
+        assert_eq!(p["typeTest"]["moments"][0], moment);
+    }
+}
+
+#[actix_rt::test]
+async fn filter_by_timestamps() {
+    let gql_query = "
+        query {
+          typeTests(where: { happened: { eq: \"2021-01-01T08:00:00.5Z\" } }) {
+            id
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    assert_eq!(p["typeTests"], json!([{ "id": 1 }]));
+}
+
+#[actix_rt::test]
+async fn filter_operators() {
+    let gql_query = "
+        query {
+          lt: siteUsers(where: { id: { lt: 3 } }) { id }
+          range: siteUsers(where: { reputation: { gte: 30, lte: 50 } }) { id }
+          in: siteUsers(where: { id: { in: [1, 4] } }) { id }
+          notIn: siteUsers(where: { id: { notIn: [1, 4], lt: 6 } }) { id }
+          like: siteUsers(where: { displayname: { like: \"user1_\" } }) { id }
+          ilike: siteUsers(where: { displayname: { ilike: \"USER2\" } }) { id }
+          startsWith: siteUsers(where: { displayname: { startsWith: \"user4\" } }) { id }
+          includes: siteUsers(where: { displayname: { includes: \"er3\" }, id: { ne: 3 } }) { id }
+          or: siteUsers(where: { or: [{ id: { eq: 1 } }, { not: { id: { lt: 40 } } }] }) { id }
+          and: siteUsers(where: { and: [] }) { id }
+          emptyOr: siteUsers(where: { or: [] }) { id }
+          isNull: typeTests(where: { mood: { isNull: true } }) { id }
+          isNotNull: typeTests(where: { price: { isNull: false, gt: \"1\" }, code: { includes: \"b\" } }) { id }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let ids = |key: &str| {
+        let mut ids = p[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect::<Vec<i64>>();
+        ids.sort_unstable();
+        ids
+    };
+    assert_eq!(ids("lt"), vec![1, 2]);
+    assert_eq!(ids("range"), vec![3, 4, 5]);
+    assert_eq!(ids("in"), vec![1, 4]);
+    assert_eq!(ids("notIn"), vec![2, 3, 5]);
+    assert_eq!(ids("like"), (10..20).collect::<Vec<i64>>());
+    assert_eq!(ids("ilike"), vec![2]);
+    assert_eq!(ids("startsWith"), vec![4, 40]);
+    assert_eq!(ids("includes"), (30..40).collect::<Vec<i64>>());
+    assert_eq!(ids("or"), vec![1, 40]);
+    assert_eq!(ids("and").len(), 40);
+    assert_eq!(ids("emptyOr"), Vec::<i64>::new());
+    assert_eq!(ids("isNull"), vec![2]);
+    assert_eq!(ids("isNotNull"), vec![1]);
+}
+
+#[actix_rt::test]
+async fn null_filter_operands() {
+    //null operands and filters don't constrain their columns
+    let gql_query = "
+        query {
+          eq: siteUsers(where: { id: { eq: null, lt: 3 } }) { id }
+          isNull: siteUsers(where: { id: { isNull: null, in: null } }) { id }
+          filter: siteUsers(where: { id: null }) { id }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    assert_eq!(p["eq"], json!([{ "id": 1 }, { "id": 2 }]));
+    assert_eq!(p["isNull"].as_array().unwrap().len(), 40);
+    assert_eq!(p["filter"].as_array().unwrap().len(), 40);
+}
+
+#[actix_rt::test]
+async fn relation_filters() {
+    let gql_query = "
+        query {
+          some: siteUsers(where: { postsByOwneruserid: { some: { score: { gt: 15 } } } }) { id }
+          every: siteUsers(where: { postsByOwneruserid: { every: { score: { gte: 3 } }, some: {} } }) { id }
+          none: siteUsers(where: { postsByOwneruserid: { none: {} } }) { id }
+          parent: posts(where: { siteUserByOwneruserid: { displayname: { eq: \"user1\" } } }) { id }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let ids = |key: &str| {
+        let mut ids = p[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect::<Vec<i64>>();
+        ids.sort_unstable();
+        ids
+    };
+    assert_eq!(ids("some"), vec![4, 8, 12, 17, 21, 25, 29]);
+    assert_eq!(ids("every"), vec![4, 7, 8, 12, 16, 17, 19, 21, 25, 29]);
+    //users without posts
+    assert_eq!(ids("none"), (31..41).collect::<Vec<i64>>());
+    assert_eq!(ids("parent"), vec![30, 60, 120]);
+}
+
+#[actix_rt::test]
+async fn nested_where() {
+    let gql_query = "
+        query {
+          siteUser(id: 1) {
+            postsByOwneruserid(where: { score: { gt: 5 } }) {
+              id
+            }
+          }
+          siteUsers(where: { id: { in: [2, 7] } }) {
+            id
+            postsByOwneruserid(where: { or: [{ score: { lt: 2 } }, { id: { eq: 91 } }] }) {
+              id
+            }
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let mut posts = p["siteUser"]["postsByOwneruserid"]
+        .as_array()
+        .unwrap()
+        .clone();
+    posts.sort_by_key(|post| post["id"].as_i64());
+    assert_eq!(posts, vec![json!({ "id": 30 }), json!({ "id": 60 })]);
+    //the parents are kept even when none of their children match
+    let mut users = p["siteUsers"].as_array().unwrap().clone();
+    users.sort_by_key(|user| user["id"].as_i64());
+    assert_eq!(users[0]["id"], 2);
+    let mut posts = users[0]["postsByOwneruserid"].as_array().unwrap().clone();
+    posts.sort_by_key(|post| post["id"].as_i64());
+    assert_eq!(posts, vec![json!({ "id": 1 }), json!({ "id": 91 })]);
+    assert_eq!(users[1], json!({ "id": 7, "postsByOwneruserid": [] }));
+}
+
+#[actix_rt::test]
+async fn order_by() {
+    let gql_query = "
+        query {
+          reputation: siteUsers(where: { id: { lt: 4 } }, orderBy: REPUTATION_DESC) { id }
+          nested: siteUsers(where: { id: { in: [1, 2] } }, orderBy: [ID_DESC]) {
+            id
+            postsByOwneruserid(orderBy: [SCORE_DESC]) { id }
+          }
+          columns: posts(where: { owneruserid: { in: [1, 2] } }, orderBy: [OWNERUSERID_ASC, SCORE_ASC]) { id }
+          nullsFirst: typeTests(orderBy: MOOD_ASC_NULLS_FIRST) { id }
+          nullsLast: typeTests(orderBy: MOOD_ASC) { id }
+          repeated: siteUsers(where: { id: { lt: 4 } }, orderBy: [ID_DESC, ID_DESC, ID_ASC]) { id }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let ids = |rows: &Value| {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect::<Vec<i64>>()
+    };
+    assert_eq!(ids(&p["reputation"]), vec![3, 2, 1]);
+    assert_eq!(ids(&p["nested"]), vec![2, 1]);
+    assert_eq!(
+        ids(&p["nested"][0]["postsByOwneruserid"]),
+        vec![31, 61, 91, 1]
+    );
+    assert_eq!(
+        ids(&p["nested"][1]["postsByOwneruserid"]),
+        vec![30, 60, 120]
+    );
+    assert_eq!(ids(&p["columns"]), vec![120, 60, 30, 1, 91, 61, 31]);
+    assert_eq!(ids(&p["nullsFirst"]), vec![2, 1]);
+    assert_eq!(ids(&p["nullsLast"]), vec![1, 2]);
+    //a column is ordered by the first of its values
+    assert_eq!(ids(&p["repeated"]), vec![3, 2, 1]);
+}
+
+#[actix_rt::test]
+async fn pagination() {
+    let gql_query = "
+        query {
+          siteUsers(first: 10, offset: 2) {
+            id
+            postsByOwneruserid(first: 3, orderBy: SCORE_DESC) { score }
+          }
+          ordered: siteUsers(first: 2, orderBy: [REPUTATION_DESC], where: { id: { lt: 20 } }) { id }
+          skipped: posts(offset: 118) { id }
+          none: siteUsers(first: 0) { id }
+          nested: siteUser(id: 2) {
+            postsByOwneruserid(offset: 1, first: 2, where: { score: { gt: 1 } }) { id }
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let users = p["siteUsers"].as_array().unwrap();
+    //every user is returned once, however many posts were joined to it
+    assert_eq!(
+        users
+            .iter()
+            .map(|user| user["id"].as_i64().unwrap())
+            .collect::<Vec<i64>>(),
+        (3..13).collect::<Vec<i64>>()
+    );
+    for user in users {
+        let scores = user["postsByOwneruserid"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|post| post["score"].as_i64().unwrap())
+            .collect::<Vec<i64>>();
+        assert!(scores.len() <= 3, "{:?}", user);
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+    assert_eq!(p["ordered"], json!([{ "id": 19 }, { "id": 18 }]));
+    assert_eq!(p["skipped"], json!([{ "id": 119 }, { "id": 120 }]));
+    assert_eq!(p["none"], json!([]));
+    //user 2's posts with a score above 1 are 31, 61 and 91
+    assert_eq!(
+        p["nested"]["postsByOwneruserid"],
+        json!([{ "id": 61 }, { "id": 91 }])
+    );
+}
+
+#[actix_rt::test]
+async fn connections() {
+    let gql_query = "
+        query {
+          siteUsersConnection(first: 2, where: { id: { lt: 6 } }, orderBy: REPUTATION_DESC) {
+            __typename
+            totalCount
+            edges { cursor node { id postsByOwneruserid(first: 1) { id } } }
+            pageInfo { hasNextPage hasPreviousPage startCursor endCursor }
+          }
+          empty: siteUsersConnection(where: { id: { gt: 100 } }) {
+            totalCount
+            nodes { id }
+            pageInfo { hasNextPage hasPreviousPage startCursor endCursor }
+          }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let connection = &p["siteUsersConnection"];
+    assert_eq!(connection["__typename"], "SiteUsersConnection");
+    assert_eq!(connection["totalCount"], 5);
+    let edges = connection["edges"].as_array().unwrap();
+    assert_eq!(edges[0]["node"]["id"], 5);
+    assert_eq!(edges[1]["node"]["id"], 4);
+    assert_eq!(
+        edges[0]["node"]["postsByOwneruserid"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    let page_info = &connection["pageInfo"];
+    assert_eq!(page_info["hasNextPage"], true);
+    assert_eq!(page_info["hasPreviousPage"], false);
+    assert_eq!(page_info["startCursor"], edges[0]["cursor"]);
+    assert_eq!(page_info["endCursor"], edges[1]["cursor"]);
+    assert_eq!(
+        p["empty"],
+        json!({
+            "totalCount": 0,
+            "nodes": [],
+            "pageInfo": { "hasNextPage": false, "hasPreviousPage": false, "startCursor": null, "endCursor": null }
+        })
+    );
+
+    //the next page starts after the end of this one, and the page before it ends before its start
+    let end_cursor = page_info["endCursor"].as_str().unwrap();
+    let gql_query = format!(
+        "
+        query {{
+          next: siteUsersConnection(first: 2, after: \"{0}\", where: {{ id: {{ lt: 6 }} }}, orderBy: REPUTATION_DESC) {{
+            nodes {{ id }}
+            pageInfo {{ hasNextPage hasPreviousPage }}
+          }}
+          rest: siteUsersConnection(after: \"{0}\", where: {{ id: {{ lt: 6 }} }}, orderBy: REPUTATION_DESC) {{
+            nodes {{ id }}
+            pageInfo {{ hasNextPage hasPreviousPage }}
+          }}
+          previous: siteUsersConnection(last: 1, before: \"{0}\", where: {{ id: {{ lt: 6 }} }}, orderBy: REPUTATION_DESC) {{
+            nodes {{ id }}
+            pageInfo {{ hasNextPage hasPreviousPage }}
+          }}
+          last: siteUsersConnection(last: 2) {{
+            nodes {{ id }}
+            pageInfo {{ hasNextPage hasPreviousPage }}
+          }}
+        }}",
+        end_cursor
+    );
+    let (_, _, p) = convert_gql(&gql_query).await;
+    let page = |nodes: &[i64], has_next_page: bool, has_previous_page: bool| {
+        json!({
+            "nodes": nodes.iter().map(|id| json!({ "id": id })).collect::<Vec<Value>>(),
+            "pageInfo": { "hasNextPage": has_next_page, "hasPreviousPage": has_previous_page }
+        })
+    };
+    assert_eq!(p["next"], page(&[3, 2], true, true));
+    assert_eq!(p["rest"], page(&[3, 2, 1], false, true));
+    assert_eq!(p["previous"], page(&[5], true, false));
+    assert_eq!(p["last"], page(&[39, 40], false, true));
+}
+
+#[actix_rt::test]
+async fn connections_over_nullable_columns() {
+    //the cursor of user 5 (whose age is 25) when ordered by age. User 4 has no age, so it comes
+    //after the cursor although it is NULL in the compared column
+    let cursor = base64::encode("[\"25\", \"5\"]");
+    let gql_query = format!(
+        "
+        query {{
+          siteUsersConnection(before: \"{}\", where: {{ id: {{ in: [1, 2, 3, 4] }} }}, orderBy: AGE_ASC) {{
+            nodes {{ id }}
+            pageInfo {{ hasNextPage hasPreviousPage }}
+          }}
+        }}",
+        cursor
+    );
+    let (_, _, p) = convert_gql(&gql_query).await;
+    assert_eq!(
+        p["siteUsersConnection"],
+        json!({
+            "nodes": [{ "id": 1 }, { "id": 2 }, { "id": 3 }],
+            "pageInfo": { "hasNextPage": true, "hasPreviousPage": false }
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn node_ids() {
+    let gql_query = "
+        query {
+          siteUser(id: 3) { nodeId }
+          compoundTable(parentId1: 1, parentId2: 2) { nodeId }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    let user_id = p["siteUser"]["nodeId"].as_str().unwrap().to_string();
+    let compound_id = p["compoundTable"]["nodeId"].as_str().unwrap().to_string();
+    let decode =
+        |node_id: &str| serde_json::from_slice::<Value>(&base64::decode(node_id).unwrap()).unwrap();
+    assert_eq!(decode(&user_id), json!(["site_user", "3"]));
+    assert_eq!(decode(&compound_id), json!(["compound_table", "1", "2"]));
+
+    //only the fragments on the type of the node (or on Node) are selected
+    let gql_query = format!(
+        "
+        query {{
+          byNodeId: siteUser(nodeId: \"{0}\") {{ id }}
+          node(nodeId: \"{0}\") {{
+            __typename
+            ... on Node {{ nodeId }}
+            ... on SiteUser {{ id }}
+            ... on Post {{ title }}
+            ...compoundFields
+          }}
+          compound: node(nodeId: \"{1}\") {{ ...compoundFields ... on SiteUser {{ id }} }}
+        }}
+        fragment compoundFields on CompoundTable {{ name }}",
+        user_id, compound_id
+    );
+    let (_, _, p) = convert_gql(&gql_query).await;
+    assert_eq!(p["byNodeId"], json!({ "id": 3 }));
+    assert_eq!(
+        p["node"],
+        json!({ "__typename": "SiteUser", "nodeId": user_id, "id": 3 })
+    );
+    assert_eq!(p["compound"], json!({ "name": "b" }));
+
+    let gql_query = format!(
+        "
+        mutation {{
+          updateTypeTest(nodeId: \"{}\", patch: {{ mood: SAD }}) {{ id mood }}
+        }}",
+        base64::encode("[\"type_test\", \"2\"]")
+    );
+    let p = convert_gql_rolled_back(&gql_query).await;
+    assert_eq!(p["updateTypeTest"], json!({ "id": 2, "mood": "SAD" }));
+}
+
+#[actix_rt::test]
+async fn unique_constraints() {
+    let gql_query = "
+        query {
+          siteUserByAccountid(accountid: 1003) { id }
+          missing: siteUserByAccountid(accountid: 3) { id }
+          compoundTableByParentId1AndName(parentId1: 1, name: \"b\") { parentId2 }
+        }";
+    let (_, _, p) = convert_gql(gql_query).await;
+    assert_eq!(p["siteUserByAccountid"], json!({ "id": 3 }));
+    assert!(p["missing"].is_null());
+    assert_eq!(
+        p["compoundTableByParentId1AndName"],
+        json!({ "parentId2": 2 })
+    );
+
+    let gql_query = "
+        mutation {
+          updateCompoundTableByParentId1AndName(parentId1: 1, name: \"b\", patch: { name: \"c\" }) {
+            parentId2
+            name
+          }
+        }";
+    let p = convert_gql_rolled_back(gql_query).await;
+    assert_eq!(
+        p["updateCompoundTableByParentId1AndName"],
+        json!({ "parentId2": 2, "name": "c" })
+    );
+}
+
+#[actix_rt::test]
+async fn connection_without_rows() {
+    let (schema, _) = get_schema_and_client().await;
+    let gql_query = "
+        query {
+          siteUsersConnection(first: 1) { totalCount pageInfo { hasNextPage } }
+        }";
+    let ctx = match schema
+        .parse_graphql(gql_query, &Map::new(), None)
+        .unwrap()
+        .remove(0)
+    {
+        RootField::Sql(ctx) => ctx,
+        RootField::Introspection(_, _) => panic!("Wasn't SQL"),
+    };
+    let err = JsonBuilder::new()
+        .add_root_field(&ctx, &[])
+        .expect_err("Wasn't Err");
+    assert_eq!(
+        err.to_string(),
+        "The query of the connection returned no rows"
+    );
+}
+
+#[actix_rt::test]
+async fn node_id_collisions() {
+    //the table named node has its query and type renamed, and its node_id column too
+    let node_id = base64::encode("[\"node\", \"1\"]");
+    let gql_query = format!(
+        "
+        query {{
+          nodeByPrimaryKey(id: 1) {{ __typename nodeId nodeIdColumn name }}
+          nodes(where: {{ nodeIdColumn: {{ eq: 7 }} }}) {{ id }}
+          node(nodeId: \"{}\") {{ ... on NodeRow {{ nodeIdColumn }} }}
+        }}",
+        node_id
+    );
+    let (_, _, p) = convert_gql(&gql_query).await;
+    assert_eq!(
+        p["nodeByPrimaryKey"],
+        json!({ "__typename": "NodeRow", "nodeId": node_id, "nodeIdColumn": 7, "name": "first" })
+    );
+    assert_eq!(p["nodes"], json!([{ "id": 1 }]));
+    assert_eq!(p["node"], json!({ "nodeIdColumn": 7 }));
+}